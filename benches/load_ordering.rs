@@ -0,0 +1,56 @@
+use aarc::{Arc, AtomicArc, Snapshot};
+use std::sync::atomic::Ordering::SeqCst;
+use std::time::{Duration, Instant};
+
+const LIST_LEN: usize = 1_000;
+const ITERS: usize = 1_000;
+
+struct ListNode {
+    val: usize,
+    next: AtomicArc<Self>,
+}
+
+fn build_list() -> AtomicArc<ListNode> {
+    let head = AtomicArc::default();
+    for val in 0..LIST_LEN {
+        let node = Arc::new(ListNode {
+            val,
+            next: AtomicArc::default(),
+        });
+        node.next
+            .store(head.load::<Snapshot<_>>(SeqCst).as_ref(), SeqCst);
+        head.store(Some(&node), SeqCst);
+    }
+    head
+}
+
+type Load = fn(&AtomicArc<ListNode>) -> Option<Snapshot<ListNode>>;
+
+fn traverse(head: &AtomicArc<ListNode>, load: Load) -> usize {
+    let mut sum = 0;
+    let mut curr = load(head);
+    while let Some(node) = curr {
+        sum += node.val;
+        curr = load(&node.next);
+    }
+    sum
+}
+
+fn measure(head: &AtomicArc<ListNode>, load: Load) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        assert_eq!(traverse(head, load), LIST_LEN * (LIST_LEN - 1) / 2);
+    }
+    start.elapsed() / (ITERS * LIST_LEN) as u32
+}
+
+fn main() {
+    let head = build_list();
+    // Warm up the thread's snapshot slots so that neither measurement pays for allocating them.
+    traverse(&head, |atomic| atomic.load(SeqCst));
+
+    let seq_cst = measure(&head, |atomic| atomic.load(SeqCst));
+    let acquire = measure(&head, AtomicArc::load_acquire);
+    println!("load(SeqCst):   {seq_cst:?} per node");
+    println!("load_acquire(): {acquire:?} per node");
+}
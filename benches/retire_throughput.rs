@@ -0,0 +1,33 @@
+use aarc::{Arc, AtomicArc};
+use std::sync::atomic::Ordering::SeqCst;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const RETIRES_PER_THREAD: usize = 200_000;
+
+/// Returns the average time per retirement while `threads` threads each replace the value of
+/// their own `AtomicArc`. Every store retires the previous value, and dispatching a full batch
+/// iterates over every thread's slot and snapshot entries.
+fn measure(threads: usize) -> Duration {
+    let start = Instant::now();
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| {
+                let atomic = AtomicArc::new(Some(0));
+                for i in 0..RETIRES_PER_THREAD {
+                    atomic.store(Some(&Arc::new(i)), SeqCst);
+                }
+            });
+        }
+    });
+    start.elapsed() / (threads * RETIRES_PER_THREAD) as u32
+}
+
+fn main() {
+    // Claim the slots up front so that no measurement pays for allocating them.
+    measure(8);
+
+    for threads in [1, 2, 4, 8] {
+        println!("{threads} thread(s): {:?} per retirement", measure(threads));
+    }
+}
@@ -0,0 +1,34 @@
+use aarc::{AtomicArc, Snapshot};
+use std::sync::atomic::Ordering::SeqCst;
+use std::time::{Duration, Instant};
+
+const SNAPSHOTS_PER_ITER: usize = 64;
+const ITERS: usize = 10_000;
+
+fn load_snapshots(atomic: &AtomicArc<usize>) -> Vec<Snapshot<usize>> {
+    (0..SNAPSHOTS_PER_ITER)
+        .map(|_| atomic.load(SeqCst).unwrap())
+        .collect()
+}
+
+fn measure<F: Fn(Vec<Snapshot<usize>>)>(atomic: &AtomicArc<usize>, release: F) -> Duration {
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERS {
+        let snapshots = load_snapshots(atomic);
+        let start = Instant::now();
+        release(snapshots);
+        total += start.elapsed();
+    }
+    total / (ITERS * SNAPSHOTS_PER_ITER) as u32
+}
+
+fn main() {
+    let atomic = AtomicArc::new(Some(53));
+    // Warm up the thread's snapshot slots so that neither measurement pays for allocating them.
+    drop(load_snapshots(&atomic));
+
+    let individual = measure(&atomic, drop);
+    let batched = measure(&atomic, Snapshot::release_all);
+    println!("drop each snapshot:    {individual:?} per snapshot");
+    println!("Snapshot::release_all: {batched:?} per snapshot");
+}
@@ -0,0 +1,137 @@
+use crate::smr::drc::Retire;
+use crate::Arc;
+use core::any::{Any, TypeId};
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::NonNull;
+
+default_reclaimer! {
+    /// An [`Arc`] whose type has been erased, which can be downcast back to the concrete type.
+    ///
+    /// [`Arc`] requires `T` to be [`Sized`], so `Arc<dyn Any + Send + Sync>` is not possible.
+    /// `AnyArc` instead stores the [`TypeId`] of the value alongside a type-erased `Arc`, and checks
+    /// it in [`downcast`][`AnyArc::downcast`], like [`std::sync::Arc::downcast`].
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::{AnyArc, Arc};
+    ///
+    /// let configs = [AnyArc::from(Arc::new(53_u32)), AnyArc::from(Arc::new("name"))];
+    /// assert_eq!(configs[0].downcast_ref::<u32>(), Some(&53));
+    /// assert!(!configs[1].is::<u32>());
+    ///
+    /// let [number, name] = configs;
+    /// let name = name.downcast::<&str>().unwrap();
+    /// assert_eq!(*name, "name");
+    /// assert!(number.downcast::<i32>().is_err());
+    /// ```
+    pub struct AnyArc<R: Retire> {
+        ptr: NonNull<()>,
+        type_id: TypeId,
+        // Monomorphized for the erased `T`.
+        clone: unsafe fn(*const ()),
+        drop: unsafe fn(*const ()),
+        phantom_r: PhantomData<R>,
+    }
+}
+
+impl<R: Retire> AnyArc<R> {
+    /// Returns `true` if the value is of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+    /// Returns a reference to the value if it is of type `T`, or [`None`] if it isn't.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.is::<T>()
+            .then(|| unsafe { &*self.ptr.as_ptr().cast::<T>() })
+    }
+    /// Converts back to an [`Arc`] if the value is of type `T`, or returns `self` if it isn't.
+    pub fn downcast<T: Any>(self) -> Result<Arc<T, R>, Self> {
+        if !self.is::<T>() {
+            return Err(self);
+        }
+        let ptr = self.ptr.as_ptr().cast::<T>();
+        mem::forget(self);
+        Ok(unsafe { Arc::from_raw(ptr) })
+    }
+    /// Returns the [`TypeId`] of the value.
+    pub fn value_type_id(&self) -> TypeId {
+        self.type_id
+    }
+}
+
+impl<R: Retire> Clone for AnyArc<R> {
+    fn clone(&self) -> Self {
+        unsafe {
+            (self.clone)(self.ptr.as_ptr());
+        }
+        Self {
+            ptr: self.ptr,
+            type_id: self.type_id,
+            clone: self.clone,
+            drop: self.drop,
+            phantom_r: PhantomData,
+        }
+    }
+}
+
+impl<R: Retire> Debug for AnyArc<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyArc")
+            .field("type_id", &self.type_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Retire> Drop for AnyArc<R> {
+    fn drop(&mut self) {
+        unsafe {
+            (self.drop)(self.ptr.as_ptr());
+        }
+    }
+}
+
+impl<T: Any + Send + Sync, R: Retire> From<Arc<T, R>> for AnyArc<R> {
+    fn from(arc: Arc<T, R>) -> Self {
+        unsafe fn clone<T: 'static, R: Retire>(ptr: *const ()) {
+            Arc::<T, R>::increment_strong_count(ptr.cast());
+        }
+        unsafe fn drop<T: 'static, R: Retire>(ptr: *const ()) {
+            mem::drop(Arc::<T, R>::from_raw(ptr.cast()));
+        }
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(Arc::into_raw(arc).cast_mut().cast()) },
+            type_id: TypeId::of::<T>(),
+            clone: clone::<T, R>,
+            drop: drop::<T, R>,
+            phantom_r: PhantomData,
+        }
+    }
+}
+
+// The value is `Send + Sync`, as required by `From`.
+unsafe impl<R: Retire> Send for AnyArc<R> {}
+
+unsafe impl<R: Retire> Sync for AnyArc<R> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnyArc, Arc};
+
+    #[test]
+    fn test_any_arc_counts() {
+        let x = Arc::new(53_u64);
+        let any = AnyArc::from(x.clone());
+        let any_clone = any.clone();
+        assert_eq!(Arc::strong_count(&x), 3);
+        assert_eq!(format!("{any:?}").split_once(' ').unwrap().0, "AnyArc");
+
+        let any = any.downcast::<u32>().unwrap_err();
+        assert_eq!(Arc::strong_count(&x), 3);
+        let y = any.downcast::<u64>().unwrap();
+        assert!(Arc::ptr_eq(&x, &y));
+        drop(any_clone);
+        assert_eq!(Arc::strong_count(&x), 2);
+    }
+}
@@ -1,50 +1,61 @@
 use crate::shared_ptrs::{ArcInner, AsPtr, CloneFromRaw, TryCloneFromRaw};
-use crate::smr::drc::{Protect, ProtectPtr, Retire};
+use crate::smr::drc::{CriticalSection, Protect, ProtectPtr, Retire};
+#[cfg(feature = "std")]
 use crate::smr::standard_reclaimer::StandardReclaimer;
+use crate::utils::sync::AtomicPtr;
 use crate::{Arc, Snapshot, Weak};
-use std::marker::PhantomData;
-use std::ptr;
-use std::ptr::{null, null_mut};
-use std::sync::atomic::Ordering::SeqCst;
-use std::sync::atomic::{AtomicPtr, Ordering};
-
-/// An atomically updatable [`Arc`].
-///
-/// This struct can be considered a cross between [`Arc`] and [`AtomicPtr`]. It contributes to the
-/// strong count, but does not implement `Deref`. Note that [`Arc`] must point to something, but
-/// `AtomicArc` can be null, in which case `load`ing it will return [`None`].
-///
-/// `AtomicArc` is compatable with both [`Arc`] and [`Snapshot`], as most methods accept
-/// [`Strong`], which represents an owned pointer that prevents deallocation.
-///
-/// # Examples
-/// ```
-/// use std::sync::atomic::Ordering::SeqCst;
-/// use aarc::{Arc, AtomicArc, Snapshot};
-///
-/// let atomic = AtomicArc::new(Some(53)); // +1 strong count on val 53
-///
-/// let snapshot53 = atomic.load::<Snapshot<_>>(SeqCst); // snapshot doesn't affect counts
-/// assert_eq!(*snapshot53.unwrap(), 53);
-///
-/// let arc53 = atomic.load::<Arc<_>>(SeqCst).unwrap(); // +1 strong count on val 53
-/// assert_eq!(*arc53, 53);
-/// assert_eq!(Arc::strong_count(&arc53), 2);
-///
-/// let arc75 = Arc::new(75); // +1 strong count on val 75
-/// atomic.store(Some(&arc75), SeqCst); // +1 strong on 75, -1 strong on 53
-/// assert_eq!(Arc::strong_count(&arc53), 1);
-/// assert_eq!(Arc::strong_count(&arc75), 2);
-///
-/// let snapshot75 = atomic.load::<Snapshot<_>>(SeqCst);
-/// assert_eq!(*snapshot75.unwrap(), 75);
-/// ```
-pub struct AtomicArc<T: 'static, R: Protect + Retire = StandardReclaimer> {
-    ptr: AtomicPtr<T>,
-    phantom: PhantomData<T>,
-    phantom_r: PhantomData<R>,
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{null, null_mut};
+use core::sync::atomic::Ordering;
+use core::sync::atomic::Ordering::{Acquire, Relaxed, SeqCst};
+use core::{mem, ptr};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+default_reclaimer! {
+    /// An atomically updatable [`Arc`].
+    ///
+    /// This struct can be considered a cross between [`Arc`] and [`AtomicPtr`]. It contributes to the
+    /// strong count, but does not implement `Deref`. Note that [`Arc`] must point to something, but
+    /// `AtomicArc` can be null, in which case `load`ing it will return [`None`]. In other words, an
+    /// `AtomicArc<T>` behaves like an atomic `Option<Arc<T>>`; use [`AtomicArc::null`] to create an
+    /// empty one.
+    ///
+    /// `AtomicArc` is compatable with both [`Arc`] and [`Snapshot`], as most methods accept
+    /// [`Strong`], which represents an owned pointer that prevents deallocation.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// let atomic = AtomicArc::new(Some(53)); // +1 strong count on val 53
+    ///
+    /// let snapshot53 = atomic.load::<Snapshot<_>>(SeqCst); // snapshot doesn't affect counts
+    /// assert_eq!(*snapshot53.unwrap(), 53);
+    ///
+    /// let arc53 = atomic.load::<Arc<_>>(SeqCst).unwrap(); // +1 strong count on val 53
+    /// assert_eq!(*arc53, 53);
+    /// assert_eq!(Arc::strong_count(&arc53), 2);
+    ///
+    /// let arc75 = Arc::new(75); // +1 strong count on val 75
+    /// atomic.store(Some(&arc75), SeqCst); // +1 strong on 75, -1 strong on 53
+    /// assert_eq!(Arc::strong_count(&arc53), 1);
+    /// assert_eq!(Arc::strong_count(&arc75), 2);
+    ///
+    /// let snapshot75 = atomic.load::<Snapshot<_>>(SeqCst);
+    /// assert_eq!(*snapshot75.unwrap(), 75);
+    /// ```
+    pub struct AtomicArc<T: 'static, R: Protect + Retire> {
+        ptr: AtomicPtr<T>,
+        phantom: PhantomData<T>,
+        phantom_r: PhantomData<R>,
+    }
 }
 
+#[cfg(feature = "std")]
 impl<T: 'static> AtomicArc<T, StandardReclaimer> {
     /// Similar to [`Arc::new`], but [`None`] is a valid input, in which case the `AtomicArc` will
     /// be empty to represent a null pointer.
@@ -61,6 +72,39 @@ impl<T: 'static> AtomicArc<T, StandardReclaimer> {
 }
 
 impl<T: 'static, R: Protect + Retire> AtomicArc<T, R> {
+    /// Creates a null `AtomicArc`. Unlike `Default`, this works with any reclaimer, and it is a
+    /// `const fn`, so it can initialize a `static`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc};
+    ///
+    /// static HEAD: AtomicArc<usize> = AtomicArc::null();
+    ///
+    /// assert!(HEAD.is_none(SeqCst));
+    /// HEAD.store(Some(&Arc::new(53)), SeqCst);
+    /// assert_eq!(*HEAD.take(SeqCst).unwrap(), 53);
+    /// ```
+    #[cfg(not(loom))]
+    pub const fn null() -> Self {
+        Self {
+            ptr: AtomicPtr::new(null_mut()),
+            phantom: PhantomData,
+            phantom_r: PhantomData,
+        }
+    }
+
+    /// Creates a null `AtomicArc`. Loom's atomics can't be created in a `const fn`.
+    #[cfg(loom)]
+    pub fn null() -> Self {
+        Self {
+            ptr: AtomicPtr::new(null_mut()),
+            phantom: PhantomData,
+            phantom_r: PhantomData,
+        }
+    }
+
     /// Stores `new`'s pointer into `self` if `self` and `current` point to the same allocation.
     ///
     /// If the comparison succeeds, the return value will be an [`Ok`] containing the unit type
@@ -76,41 +120,321 @@ impl<T: 'static, R: Protect + Retire> AtomicArc<T, R> {
         C: Strong<T>,
         N: Strong<T>,
         V: Strong<T>,
+    {
+        self.compare_exchange_with(
+            current,
+            new,
+            |c, n| self.ptr.compare_exchange(c, n, success, failure),
+            Self::clone_failure,
+        )
+        .map(drop)
+    }
+
+    /// Similar to [`compare_exchange`][`AtomicArc::compare_exchange`], except that it is allowed
+    /// to fail spuriously even when `self` and `current` point to the same allocation, which can
+    /// result in more efficient code on some platforms. It is intended for use in retry loops.
+    ///
+    /// See [`AtomicPtr::compare_exchange_weak`] for more details.
+    pub fn compare_exchange_weak<C, N, V>(
+        &self,
+        current: Option<&C>,
+        new: Option<&N>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(), Option<V>>
+    where
+        C: Strong<T>,
+        N: Strong<T>,
+        V: Strong<T>,
+    {
+        self.compare_exchange_with(
+            current,
+            new,
+            |c, n| self.ptr.compare_exchange_weak(c, n, success, failure),
+            Self::clone_failure,
+        )
+        .map(drop)
+    }
+
+    /// Similar to [`compare_exchange`][`AtomicArc::compare_exchange`], except that on failure,
+    /// the [`Err`] variant contains the pointer that was observed instead of a protected copy of
+    /// it. This avoids the cost of protection when the caller only needs to know whether (and to
+    /// what address) the value has changed, e.g. to decide whether to back off.
+    ///
+    /// The observed pointer is not protected, so the object that it points to may be reclaimed at
+    /// any time. It must not be dereferenced; to access the value, [`load`][`AtomicArc::load`] it
+    /// again.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc};
+    ///
+    /// let x = Arc::new(1);
+    /// let atomic = AtomicArc::from(&x);
+    /// let y = Arc::new(2);
+    ///
+    /// assert!(atomic.compare_exchange_raw(Some(&x), Some(&y), SeqCst, SeqCst).is_ok());
+    /// let observed = atomic
+    ///     .compare_exchange_raw(Some(&x), None::<&Arc<_>>, SeqCst, SeqCst)
+    ///     .unwrap_err();
+    /// assert_eq!(observed, Arc::as_ptr(&y));
+    /// ```
+    pub fn compare_exchange_raw<C, N>(
+        &self,
+        current: Option<&C>,
+        new: Option<&N>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(), *const T>
+    where
+        C: Strong<T>,
+        N: Strong<T>,
+    {
+        self.compare_exchange_with(
+            current,
+            new,
+            |c, n| self.ptr.compare_exchange(c, n, success, failure),
+            |before| before.cast_const(),
+        )
+        .map(drop)
+    }
+
+    /// Stores `new`'s pointer into `self` if `self` and `current` point to the same allocation,
+    /// and returns the displaced value.
+    ///
+    /// This is similar to [`compare_exchange`][`AtomicArc::compare_exchange`], except that on
+    /// success, the [`Ok`] variant contains an [`Arc`] to the previous value (or [`None`] if it
+    /// was null), to which the strong count held by `self` is transferred. This allows the
+    /// displaced value to be reused, e.g. by returning a node to a free list.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// let atomic = AtomicArc::new(Some(1));
+    /// let current = atomic.load::<Snapshot<_>>(SeqCst);
+    /// let replacement = Arc::new(2);
+    ///
+    /// let before = atomic
+    ///     .compare_and_swap::<_, _, Snapshot<_>>(current.as_ref(), Some(&replacement), SeqCst, SeqCst)
+    ///     .ok()
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!((*before, Arc::strong_count(&before)), (1, 1));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn compare_and_swap<C, N, V>(
+        &self,
+        current: Option<&C>,
+        new: Option<&N>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Option<Arc<T, R>>, Option<V>>
+    where
+        C: Strong<T>,
+        N: Strong<T>,
+        V: Strong<T>,
+    {
+        self.compare_exchange_with(
+            current,
+            new,
+            |c, n| self.ptr.compare_exchange(c, n, success, failure),
+            Self::clone_failure,
+        )
+    }
+
+    /// Similar to [`compare_exchange`][`AtomicArc::compare_exchange`], except that `new` is
+    /// taken by value. On success, its strong count is transferred to `self`; on failure, it is
+    /// handed back alongside the observed value, so that a retry loop can reuse it instead of
+    /// creating a new one.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// let atomic = AtomicArc::new(Some(1));
+    /// let mut current = None;
+    /// let mut new = Some(Arc::new(2));
+    /// loop {
+    ///     match atomic.compare_exchange_owned::<Snapshot<_>, Snapshot<_>>(
+    ///         current.as_ref(),
+    ///         new,
+    ///         SeqCst,
+    ///         SeqCst,
+    ///     ) {
+    ///         Ok(()) => break,
+    ///         Err((actual, unused)) => (current, new) = (actual, unused),
+    ///     }
+    /// }
+    /// assert_eq!(*atomic.load::<Arc<_>>(SeqCst).unwrap(), 2);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn compare_exchange_owned<C, V>(
+        &self,
+        current: Option<&C>,
+        new: Option<Arc<T, R>>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(), (Option<V>, Option<Arc<T, R>>)>
+    where
+        C: Strong<T>,
+        V: Strong<T>,
     {
         let c: *const T = current.map_or(null(), C::as_ptr);
-        let n: *const T = new.map_or(null(), N::as_ptr);
-        match with_critical_section::<R, _, _>(|| {
+        let n: *const T = new.as_ref().map_or(null(), Arc::as_ptr);
+        // The failure value must be cloned inside the critical section, as it may otherwise be
+        // reclaimed before it is protected.
+        let result = with_critical_section::<R, _, _>(|| {
             self.ptr
                 .compare_exchange(c.cast_mut(), n.cast_mut(), success, failure)
-                .map(|before| unsafe {
-                    if ptr::eq(n, before) {
-                        null_mut()
-                    } else {
-                        if !n.is_null() {
-                            Arc::<_, R>::increment_strong_count(n);
-                        }
-                        before
-                    }
-                })
-        }) {
-            Ok(before) => unsafe {
+                .map_err(Self::clone_failure)
+        });
+        match result {
+            Ok(before) => {
+                // `self` takes over the strong count of `new` and gives up the one it held.
+                mem::forget(new);
                 if !before.is_null() {
-                    drop(Arc::<_, R>::from_raw(before));
+                    unsafe { drop(Arc::<_, R>::from_raw(before)) };
                 }
                 Ok(())
-            },
-            Err(before) => {
-                if before.is_null() {
-                    Err(None)
-                } else {
-                    unsafe { Err(Some(V::clone_from_raw(before))) }
-                }
             }
+            Err(actual) => Err((actual, new)),
+        }
+    }
+
+    /// Stores `new`'s pointer into `self` only if `self` is null, like initializing a
+    /// [`OnceCell`][`core::cell::OnceCell`]. If another value got there first, it is returned in
+    /// the [`Err`] variant as the desired type (`Arc` or `Snapshot`), so that every racing thread
+    /// ends up using the same value.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// let atomic = AtomicArc::default();
+    /// assert!(atomic.set_if_none::<_, Snapshot<_>>(&Arc::new(1), SeqCst, SeqCst).is_ok());
+    ///
+    /// let winner = atomic
+    ///     .set_if_none::<_, Snapshot<_>>(&Arc::new(2), SeqCst, SeqCst)
+    ///     .unwrap_err();
+    /// assert_eq!(*winner, 1);
+    /// ```
+    pub fn set_if_none<N: Strong<T>, V: Strong<T>>(
+        &self,
+        new: &N,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(), V> {
+        self.compare_exchange_with(
+            None::<&Arc<T, R>>,
+            Some(new),
+            |c, n| self.ptr.compare_exchange(c, n, success, failure),
+            // The comparison only fails if the pointer is not null.
+            |before| unsafe { V::clone_from_raw(before) },
+        )
+        .map(drop)
+    }
+
+    /// Moves the value of `from` into `to` and makes `from` null, provided that `from` points to
+    /// the same allocation as `expected_from` and `to` to the same allocation as `expected_to`.
+    ///
+    /// There is no double-width CAS, so this takes two steps: `to` is exchanged first, then `from`.
+    /// If the second exchange fails, `to` is restored to `expected_to`. All operations are
+    /// [`SeqCst`][`Ordering::SeqCst`]. Unlike a true double CAS, the transfer is not atomic:
+    ///
+    /// - Between the two steps, other threads can observe the value in both atomics.
+    /// - If the transfer is rolled back, other threads may have observed (and acted on) the value
+    ///   in `to` in the meantime.
+    /// - If another thread stores into `to` before the rollback, `to` is left as is (see
+    ///   [`TransferError::RollbackFailed`]).
+    ///
+    /// Algorithms that use this must therefore tolerate a value being briefly reachable from both
+    /// atomics, e.g. by treating `from` as the authoritative location until it is null.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, TransferError};
+    ///
+    /// let x = Arc::new(53);
+    /// let from = AtomicArc::from(&x);
+    /// let to = AtomicArc::default();
+    /// assert_eq!(AtomicArc::transfer(&from, &to, Some(&x), None::<&Arc<_>>), Ok(()));
+    /// assert!(from.is_none(SeqCst));
+    /// assert!(Arc::ptr_eq(&to.load::<Arc<_>>(SeqCst).unwrap(), &x));
+    ///
+    /// // `from` no longer holds `None`, so nothing is changed.
+    /// let result = AtomicArc::transfer(&to, &from, Some(&x), Some(&x));
+    /// assert_eq!(result, Err(TransferError::To));
+    /// ```
+    pub fn transfer<C, E>(
+        from: &Self,
+        to: &Self,
+        expected_from: Option<&C>,
+        expected_to: Option<&E>,
+    ) -> Result<(), TransferError>
+    where
+        C: Strong<T>,
+        E: Strong<T>,
+    {
+        to.compare_exchange_raw(expected_to, expected_from, SeqCst, SeqCst)
+            .map_err(|_| TransferError::To)?;
+        if from
+            .compare_exchange_raw(expected_from, None::<&C>, SeqCst, SeqCst)
+            .is_ok()
+        {
+            return Ok(());
+        }
+        match to.compare_exchange_raw(expected_from, expected_to, SeqCst, SeqCst) {
+            Ok(()) => Err(TransferError::From),
+            Err(_) => Err(TransferError::RollbackFailed),
         }
     }
 
+    /// Performs the exchange with `cas`. On success, the strong count previously held by `self`
+    /// is returned as an `Arc`; on failure, `on_failure` is called with the observed pointer.
+    fn compare_exchange_with<C, N, E, F, G>(
+        &self,
+        current: Option<&C>,
+        new: Option<&N>,
+        cas: F,
+        on_failure: G,
+    ) -> Result<Option<Arc<T, R>>, E>
+    where
+        C: Strong<T>,
+        N: Strong<T>,
+        F: Fn(*mut T, *mut T) -> Result<*mut T, *mut T>,
+        G: Fn(*mut T) -> E,
+    {
+        let c: *const T = current.map_or(null(), C::as_ptr);
+        let n: *const T = new.map_or(null(), N::as_ptr);
+        // The failure value must be cloned inside the critical section, as it may otherwise be
+        // reclaimed before it is protected.
+        with_critical_section::<R, _, _>(|| match cas(c.cast_mut(), n.cast_mut()) {
+            Ok(before) => unsafe {
+                if !n.is_null() {
+                    Arc::<_, R>::increment_strong_count(n);
+                }
+                Ok((!before.is_null()).then(|| Arc::<_, R>::from_raw(before)))
+            },
+            Err(before) => Err(on_failure(before)),
+        })
+    }
+
+    fn clone_failure<V: Strong<T>>(before: *mut T) -> Option<V> {
+        (!before.is_null()).then(|| unsafe { V::clone_from_raw(before) })
+    }
+
     /// Loads the pointer and returns the desired type (`Arc` or `Snapshot`), or [`None`] if it is
     /// null.
+    ///
+    /// # Panics
+    /// Panics if `order` is [`Release`][`Ordering::Release`] or [`AcqRel`][`Ordering::AcqRel`],
+    /// as with [`AtomicPtr::load`].
     pub fn load<V: Strong<T>>(&self, order: Ordering) -> Option<V> {
         with_critical_section::<R, _, _>(|| {
             let ptr = self.ptr.load(order);
@@ -122,6 +446,37 @@ impl<T: 'static, R: Protect + Retire> AtomicArc<T, R> {
         })
     }
 
+    /// Loads the pointer without protecting it or affecting the reference counts, and returns it
+    /// (null if `self` is null).
+    ///
+    /// The object that the pointer points to may be reclaimed at any time unless the current thread
+    /// is in a [`CriticalSection`] (e.g. one entered with [`pin`][`crate::pin`]) that was entered
+    /// before the load, in which case the pointer is only valid for as long as the critical section
+    /// lasts. See [`CriticalSection`] for an example.
+    pub fn load_raw(&self, order: Ordering) -> *const T {
+        self.ptr.load(order).cast_const()
+    }
+
+    /// Equivalent to [`load`][`AtomicArc::load`] with [`Acquire`][`Ordering::Acquire`] ordering.
+    ///
+    /// Rust has no stable `Consume` ordering, so `Acquire` is the minimum ordering that makes the
+    /// contents of a freshly-published pointer visible. For traversals that follow pointers to
+    /// immutable data (e.g. walking a linked list), this is sufficient and avoids the cost of a
+    /// `SeqCst` load on weakly-ordered architectures. Use `SeqCst` only if the algorithm relies on
+    /// a single total order across multiple locations.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::{AtomicArc, Snapshot};
+    ///
+    /// let atomic = AtomicArc::new(Some(53));
+    /// let snapshot = atomic.load_acquire::<Snapshot<_>>().unwrap();
+    /// assert_eq!(*snapshot, 53);
+    /// ```
+    pub fn load_acquire<V: Strong<T>>(&self) -> Option<V> {
+        self.load(Acquire)
+    }
+
     /// Stores `new`'s pointer (or [`None`]) into `self`.
     pub fn store<N: Strong<T>>(&self, new: Option<&N>, order: Ordering) {
         let ptr: *const T = new.map_or(null(), N::as_ptr);
@@ -137,6 +492,235 @@ impl<T: 'static, R: Protect + Retire> AtomicArc<T, R> {
             }
         }
     }
+
+    /// Stores `new`'s pointer into `self` and returns the desired type (`Arc` or `Snapshot`)
+    /// pointing to it, which saves a subsequent [`load`][`AtomicArc::load`].
+    ///
+    /// The pointer is protected before it is published, so the returned value remains valid even
+    /// if another thread replaces it immediately.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// let atomic = AtomicArc::new(None);
+    /// let snapshot = atomic.fetch_store::<_, Snapshot<_>>(&Arc::new(53), SeqCst);
+    /// assert_eq!(*snapshot, 53);
+    /// ```
+    pub fn fetch_store<N: Strong<T>, V: Strong<T>>(&self, new: &N, order: Ordering) -> V {
+        let protected = unsafe { V::clone_from_raw(N::as_ptr(new)) };
+        self.store(Some(new), order);
+        protected
+    }
+
+    /// Stores `new`'s pointer (or [`None`]) into `self` and returns the previous value as an
+    /// [`Arc`], or [`None`] if it was null. The strong count held by `self` is transferred to the
+    /// returned `Arc`.
+    pub fn swap<N: Strong<T>>(&self, new: Option<&N>, order: Ordering) -> Option<Arc<T, R>> {
+        let ptr: *const T = new.map_or(null(), N::as_ptr);
+        if !ptr.is_null() {
+            unsafe {
+                Arc::<_, R>::increment_strong_count(ptr);
+            }
+        }
+        let before = self.ptr.swap(ptr.cast_mut(), order);
+        if before.is_null() {
+            None
+        } else {
+            unsafe { Some(Arc::<_, R>::from_raw(before)) }
+        }
+    }
+
+    /// Stores a null pointer into `self` and returns the previous value, as with
+    /// [`swap`][`AtomicArc::swap`]`(None, order)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::AtomicArc;
+    ///
+    /// let atomic = AtomicArc::new(Some(53));
+    /// assert_eq!(*atomic.take(SeqCst).unwrap(), 53);
+    /// assert!(atomic.take(SeqCst).is_none());
+    /// ```
+    pub fn take(&self, order: Ordering) -> Option<Arc<T, R>> {
+        self.swap::<Arc<T, R>>(None, order)
+    }
+
+    /// Repeatedly applies `f` to the current value and attempts to store the result, until the
+    /// store succeeds or `f` returns [`None`]. Analogous to [`AtomicPtr::fetch_update`].
+    ///
+    /// `f` returns `Some(new)` to store `new` (where `new` may itself be [`None`] to store a null
+    /// pointer), or `None` to give up. On success, the replaced value is returned in an [`Ok`];
+    /// otherwise, the last observed value is returned in an [`Err`]. As `f` may be called several
+    /// times, it should not have side effects.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc};
+    ///
+    /// let atomic = AtomicArc::new(Some(1));
+    /// let before = atomic.fetch_update(SeqCst, SeqCst, |x| x.map(|x| Some(Arc::new(**x + 1))));
+    /// assert_eq!(*before.unwrap().unwrap(), 1);
+    ///
+    /// let before = atomic.fetch_update::<Arc<_>, _>(SeqCst, SeqCst, |x| (**x? > 5).then_some(None));
+    /// assert_eq!(*before.unwrap_err().unwrap(), 2);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn fetch_update<N, F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<Option<Snapshot<T, R>>, Option<Snapshot<T, R>>>
+    where
+        R: ProtectPtr,
+        N: Strong<T>,
+        F: FnMut(Option<&Snapshot<T, R>>) -> Option<Option<N>>,
+    {
+        let mut prev = self.load::<Snapshot<T, R>>(fetch_order);
+        while let Some(next) = f(prev.as_ref()) {
+            let c = prev.as_ref().map_or(null(), Snapshot::as_ptr).cast_mut();
+            // If the exchange failed spuriously, `prev` is still accurate and already protected,
+            // so a new protection is only acquired when the observed value has changed.
+            match self.compare_exchange_with(
+                prev.as_ref(),
+                next.as_ref(),
+                |c, n| self.ptr.compare_exchange_weak(c, n, set_order, fetch_order),
+                |before| (before != c).then(|| Self::clone_failure(before)),
+            ) {
+                Ok(_) => return Ok(prev),
+                Err(Some(actual)) => prev = actual,
+                Err(None) => {}
+            }
+        }
+        Err(prev)
+    }
+
+    /// Returns `true` if the pointer is not null.
+    ///
+    /// Unlike [`load`][`AtomicArc::load`], this does not enter a critical section or protect the
+    /// pointer, making it a cheap check to perform before committing to a `load`. As with any
+    /// lock-free read, the result may be stale as soon as it is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::{Acquire, SeqCst};
+    /// use aarc::AtomicArc;
+    ///
+    /// let atomic = AtomicArc::new(Some(53));
+    /// assert!(atomic.is_some(Acquire));
+    ///
+    /// atomic.store::<aarc::Arc<_>>(None, SeqCst);
+    /// assert!(atomic.is_none(Acquire));
+    /// ```
+    pub fn is_some(&self, order: Ordering) -> bool {
+        !self.ptr.load(order).is_null()
+    }
+
+    /// Returns `true` if the pointer is null. See [`is_some`][`AtomicArc::is_some`].
+    pub fn is_none(&self, order: Ordering) -> bool {
+        self.ptr.load(order).is_null()
+    }
+
+    /// Consumes the `AtomicArc` and returns the value that it holds, or [`None`] if it is null.
+    /// The strong count held by `self` is transferred to the returned [`Arc`], so no counts are
+    /// modified and nothing is retired.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::{Arc, AtomicArc};
+    ///
+    /// let atomic = AtomicArc::new(Some(53));
+    /// let arc = atomic.into_inner().unwrap();
+    /// assert_eq!(Arc::strong_count(&arc), 1);
+    /// ```
+    pub fn into_inner(self) -> Option<Arc<T, R>> {
+        // Ownership of `self` rules out concurrent accesses.
+        let ptr = self.ptr.load(Relaxed);
+        mem::forget(self);
+        (!ptr.is_null()).then(|| unsafe { Arc::from_raw(ptr) })
+    }
+
+    /// Returns a mutable reference to the [`Arc`] that `self` holds, or [`None`] if the pointer is
+    /// null. The exclusive borrow of `self` rules out concurrent accesses, so the `Arc` can be
+    /// replaced without any atomic operations, and the value can be mutated through
+    /// [`Arc::get_mut`] or [`Arc::make_mut`].
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::{Arc, AtomicArc};
+    ///
+    /// let mut atomic = AtomicArc::new(Some(53));
+    /// *Arc::make_mut(atomic.get_mut().unwrap()) += 1;
+    /// assert_eq!(**atomic.get_mut().unwrap(), 54);
+    ///
+    /// *atomic.get_mut().unwrap() = Arc::new(75);
+    /// assert_eq!(*atomic.into_inner().unwrap(), 75);
+    /// ```
+    #[cfg(not(loom))]
+    pub fn get_mut(&mut self) -> Option<&mut Arc<T, R>> {
+        let ptr = self.ptr.get_mut();
+        // `AtomicPtr` has the same in-memory representation as a pointer, and `Arc` is a
+        // transparent wrapper around a non-null one.
+        (!ptr.is_null()).then(|| unsafe { &mut *(ptr as *mut *mut T).cast::<Arc<T, R>>() })
+    }
+}
+
+impl<T: 'static, R: Protect + ProtectPtr + Retire> AtomicArc<T, R> {
+    /// Loads the pointer, protects it for the duration of `f`, and calls `f` with a reference to
+    /// the value (or [`None`] if it is null). Returns the result of `f`.
+    ///
+    /// This is a scoped alternative to loading a [`Snapshot`]: the protection is released before
+    /// this method returns, and the reference cannot escape the closure.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::AtomicArc;
+    ///
+    /// let atomic = AtomicArc::new(Some(53));
+    /// assert!(atomic.with(SeqCst, |x| x.is_some_and(|x| *x > 50)));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `order` is [`Release`][`Ordering::Release`] or [`AcqRel`][`Ordering::AcqRel`],
+    /// as with [`AtomicPtr::load`].
+    pub fn with<U, F: FnOnce(Option<&T>) -> U>(&self, order: Ordering, f: F) -> U {
+        let snapshot = self.load::<Snapshot<T, R>>(order);
+        f(snapshot.as_deref())
+    }
+}
+
+impl<T: 'static + Debug, R: Protect + ProtectPtr + Retire> Debug for AtomicArc<T, R> {
+    /// Formats the current value as `Some(value)`, or `None` if the pointer is null. The value is
+    /// loaded as a [`Snapshot`], so this is safe to call concurrently with updates.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.load::<Snapshot<T, R>>(SeqCst), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: 'static + Serialize, R: Protect + ProtectPtr + Retire> Serialize for AtomicArc<T, R> {
+    /// Serializes the current value as an [`Option`]. As with `Debug`, the value is loaded as a
+    /// [`Snapshot`], so this is safe to call concurrently with updates.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.load::<Snapshot<T, R>>(SeqCst).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: 'static + Deserialize<'de>, R: Protect + Retire> Deserialize<'de> for AtomicArc<T, R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ptr = Option::<Arc<T, R>>::deserialize(deserializer)?.map_or(null(), Arc::into_raw);
+        Ok(Self {
+            ptr: AtomicPtr::new(ptr.cast_mut()),
+            phantom: PhantomData,
+            phantom_r: PhantomData,
+        })
+    }
 }
 
 impl<T: 'static, R: Protect + Retire> Clone for AtomicArc<T, R> {
@@ -158,56 +742,259 @@ impl<T: 'static, R: Protect + Retire> Clone for AtomicArc<T, R> {
     }
 }
 
-impl<T: 'static> Default for AtomicArc<T, StandardReclaimer> {
+/// The error returned by [`AtomicArc::transfer`], which describes how far the transfer got.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferError {
+    /// `to` did not hold the expected value. Neither atomic was modified.
+    To,
+    /// `from` did not hold the expected value. `to` was restored to its expected value.
+    From,
+    /// `from` did not hold the expected value, and another thread modified `to` before it could be
+    /// restored, so it was left as is. `from` was not modified.
+    RollbackFailed,
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::To => "the destination did not hold the expected value",
+            Self::From => "the source did not hold the expected value",
+            Self::RollbackFailed => {
+                "the source did not hold the expected value, and the \
+                                     destination could not be restored"
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransferError {}
+
+#[cfg(feature = "std")]
+impl<T: 'static> Default for AtomicArc<T, StandardReclaimer> {
+    /// Creates a null `AtomicArc`, even if `T` implements [`Default`]. Use [`AtomicArcInit`] for
+    /// an `AtomicArc` that defaults to `Some(T::default())`.
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T: 'static, R: Protect + Retire> Drop for AtomicArc<T, R> {
+    fn drop(&mut self) {
+        let ptr = self.ptr.load(SeqCst);
+        if !ptr.is_null() {
+            unsafe {
+                drop(Arc::<_, R>::from_raw(ptr));
+            }
+        }
+    }
+}
+
+default_reclaimer! {
+    /// An [`AtomicArc`] whose [`Default`] implementation installs `T::default()` instead of null.
+    ///
+    /// `AtomicArc::default()` is always null, which can be surprising for a field of a struct that
+    /// derives [`Default`]. Wrapping the field in `AtomicArcInit` opts into a populated default. It
+    /// dereferences to the wrapped [`AtomicArc`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{AtomicArc, AtomicArcInit, Snapshot};
+    ///
+    /// #[derive(Default)]
+    /// struct Config {
+    ///     retries: usize,
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Service {
+    ///     null_config: AtomicArc<Config>,
+    ///     config: AtomicArcInit<Config>,
+    /// }
+    ///
+    /// let service = Service::default();
+    /// assert!(service.null_config.load::<Snapshot<_>>(SeqCst).is_none());
+    /// assert_eq!(service.config.load::<Snapshot<_>>(SeqCst).unwrap().retries, 0);
+    /// ```
+    pub struct AtomicArcInit<T: 'static, R: Protect + Retire>(AtomicArc<T, R>);
+}
+
+impl<T: 'static, R: Protect + Retire> AtomicArcInit<T, R> {
+    /// Unwraps the underlying [`AtomicArc`].
+    pub fn into_inner(self) -> AtomicArc<T, R> {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static + Default> Default for AtomicArcInit<T, StandardReclaimer> {
+    fn default() -> Self {
+        Self(AtomicArc::new(Some(T::default())))
+    }
+}
+
+impl<T: 'static, R: Protect + Retire> Deref for AtomicArcInit<T, R> {
+    type Target = AtomicArc<T, R>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: 'static, R: Protect + Retire> DerefMut for AtomicArcInit<T, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: 'static, R: Protect + Retire> From<AtomicArc<T, R>> for AtomicArcInit<T, R> {
+    fn from(value: AtomicArc<T, R>) -> Self {
+        Self(value)
+    }
+}
+
+default_reclaimer! {
+    /// A fixed-size array of [`AtomicArc`]s, each of which can be updated independently.
+    ///
+    /// The elements are stored inline, so a table of buckets needs no allocation besides the
+    /// values themselves. The indexed methods below cover the common operations; the array also
+    /// dereferences to `[AtomicArc<T, R>; N]` for everything else (e.g. iteration).
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArcArray, Snapshot};
+    ///
+    /// let buckets = AtomicArcArray::<usize, 4>::null();
+    /// let x = Arc::new(53);
+    /// buckets.store(1, Some(&x), SeqCst);
+    ///
+    /// assert!(buckets.load::<Snapshot<_>>(0, SeqCst).is_none());
+    /// assert_eq!(*buckets.load::<Snapshot<_>>(1, SeqCst).unwrap(), 53);
+    /// assert!(buckets
+    ///     .compare_exchange::<_, _, Arc<_>>(1, Some(&x), None::<&Arc<_>>, SeqCst, SeqCst)
+    ///     .is_ok());
+    /// assert!(buckets.iter().all(|bucket| bucket.is_none(SeqCst)));
+    /// ```
+    pub struct AtomicArcArray<T: 'static, const N: usize, R: Protect + Retire>(
+        [AtomicArc<T, R>; N],
+    );
+}
+
+impl<T: 'static, const N: usize, R: Protect + Retire> AtomicArcArray<T, N, R> {
+    /// Creates an array whose elements are all null.
+    pub fn null() -> Self {
+        Self(core::array::from_fn(|_| AtomicArc::null()))
+    }
+
+    /// Loads the element at `index`, as with [`AtomicArc::load`].
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds, or under the same conditions as [`AtomicArc::load`].
+    pub fn load<V: Strong<T>>(&self, index: usize, order: Ordering) -> Option<V> {
+        self.0[index].load(order)
+    }
+
+    /// Stores `new`'s pointer (or [`None`]) into the element at `index`, as with
+    /// [`AtomicArc::store`].
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn store<N2: Strong<T>>(&self, index: usize, new: Option<&N2>, order: Ordering) {
+        self.0[index].store(new, order);
+    }
+
+    /// Stores `new`'s pointer into the element at `index` if it points to the same allocation as
+    /// `current`, as with [`AtomicArc::compare_exchange`].
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn compare_exchange<C, N2, V>(
+        &self,
+        index: usize,
+        current: Option<&C>,
+        new: Option<&N2>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(), Option<V>>
+    where
+        C: Strong<T>,
+        N2: Strong<T>,
+        V: Strong<T>,
+    {
+        self.0[index].compare_exchange(current, new, success, failure)
+    }
+
+    /// Unwraps the underlying [`AtomicArc`]s.
+    pub fn into_inner(self) -> [AtomicArc<T, R>; N] {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static, const N: usize> Default for AtomicArcArray<T, N, StandardReclaimer> {
     fn default() -> Self {
-        Self {
-            ptr: AtomicPtr::default(),
-            phantom: PhantomData,
-            phantom_r: PhantomData,
-        }
+        Self::null()
     }
 }
 
-impl<T: 'static, R: Protect + Retire> Drop for AtomicArc<T, R> {
-    fn drop(&mut self) {
-        let ptr = self.ptr.load(SeqCst);
-        if !ptr.is_null() {
-            unsafe {
-                drop(Arc::<_, R>::from_raw(ptr));
-            }
-        }
+impl<T: 'static, const N: usize, R: Protect + Retire> Deref for AtomicArcArray<T, N, R> {
+    type Target = [AtomicArc<T, R>; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: 'static, const N: usize, R: Protect + Retire> DerefMut for AtomicArcArray<T, N, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: 'static, const N: usize, R: Protect + Retire> From<[AtomicArc<T, R>; N]>
+    for AtomicArcArray<T, N, R>
+{
+    fn from(value: [AtomicArc<T, R>; N]) -> Self {
+        Self(value)
     }
 }
 
-/// An atomically updatable [`Weak`].
-///
-/// # Examples
-/// ```
-/// use std::sync::atomic::Ordering::SeqCst;
-/// use aarc::{Arc, AtomicArc, AtomicWeak, Snapshot};
-///
-/// let arc1 = Arc::new(53); // +1 strong count
-///
-/// let atomic = AtomicWeak::default();
-/// atomic.store(Some(&arc1), SeqCst); // +1 weak count
-///
-/// let snapshot = atomic.upgrade::<Snapshot<_>>(SeqCst); // snapshot doesn't affect counts
-/// assert_eq!(*snapshot.unwrap(), 53);
-///
-/// let weak = atomic.load(SeqCst).unwrap(); // +1 weak count
-/// let arc2 = weak.upgrade().unwrap(); // +1 strong count
-/// assert_eq!(*arc2, 53);
-/// assert_eq!(Arc::strong_count(&arc2), 2);
-/// assert_eq!(Arc::weak_count(&arc2), 2);
-/// ```
-pub struct AtomicWeak<T: 'static, R: Protect + Retire = StandardReclaimer> {
-    ptr: AtomicPtr<T>,
-    phantom_r: PhantomData<R>,
+default_reclaimer! {
+    /// An atomically updatable [`Weak`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, AtomicWeak, Snapshot};
+    ///
+    /// let arc1 = Arc::new(53); // +1 strong count
+    ///
+    /// let atomic = AtomicWeak::default();
+    /// atomic.store(Some(&arc1), SeqCst); // +1 weak count
+    ///
+    /// let snapshot = atomic.upgrade::<Snapshot<_>>(SeqCst); // snapshot doesn't affect counts
+    /// assert_eq!(*snapshot.unwrap(), 53);
+    ///
+    /// let weak = atomic.load(SeqCst).unwrap(); // +1 weak count
+    /// let arc2 = weak.upgrade().unwrap(); // +1 strong count
+    /// assert_eq!(*arc2, 53);
+    /// assert_eq!(Arc::strong_count(&arc2), 2);
+    /// assert_eq!(Arc::weak_count(&arc2), 2);
+    /// ```
+    pub struct AtomicWeak<T: 'static, R: Protect + Retire> {
+        ptr: AtomicPtr<T>,
+        phantom_r: PhantomData<R>,
+    }
 }
 
 impl<T: 'static, R: Protect + Retire> AtomicWeak<T, R> {
     /// See [`AtomicArc::compare_exchange`]. This method behaves similarly, except that the return
     /// type for the failure case cannot be specified by the caller; it must be a [`Weak`].
+    ///
+    /// Pointers are compared by identity, so an entry whose value has already been dropped can
+    /// still be replaced by passing a [`Weak`] to it as `current`.
     pub fn compare_exchange<C, N>(
         &self,
         current: Option<&C>,
@@ -218,28 +1005,54 @@ impl<T: 'static, R: Protect + Retire> AtomicWeak<T, R> {
     where
         C: Shared<T>,
         N: Shared<T>,
+    {
+        self.compare_exchange_with(current, new, |c, n| {
+            self.ptr.compare_exchange(c, n, success, failure)
+        })
+    }
+
+    /// See [`AtomicArc::compare_exchange_weak`]. As with
+    /// [`compare_exchange`][`AtomicWeak::compare_exchange`], the return type for the failure case
+    /// must be a [`Weak`].
+    pub fn compare_exchange_weak<C, N>(
+        &self,
+        current: Option<&C>,
+        new: Option<&N>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(), Option<Weak<T, R>>>
+    where
+        C: Shared<T>,
+        N: Shared<T>,
+    {
+        self.compare_exchange_with(current, new, |c, n| {
+            self.ptr.compare_exchange_weak(c, n, success, failure)
+        })
+    }
+
+    fn compare_exchange_with<C, N, F>(
+        &self,
+        current: Option<&C>,
+        new: Option<&N>,
+        cas: F,
+    ) -> Result<(), Option<Weak<T, R>>>
+    where
+        C: Shared<T>,
+        N: Shared<T>,
+        F: Fn(*mut T, *mut T) -> Result<*mut T, *mut T>,
     {
         let c: *const T = current.map_or(null(), C::as_ptr);
         let n: *const T = new.map_or(null(), N::as_ptr);
-        match with_critical_section::<R, _, _>(|| {
-            self.ptr
-                .compare_exchange(c.cast_mut(), n.cast_mut(), success, failure)
-                .map(|before| unsafe {
-                    if ptr::eq(n, before) {
-                        null_mut()
-                    } else {
-                        if !n.is_null() {
-                            Weak::<_, R>::increment_weak_count(n);
-                        }
-                        before
-                    }
-                })
-        }) {
+        match with_critical_section::<R, _, _>(|| match cas(c.cast_mut(), n.cast_mut()) {
             Ok(before) => unsafe {
-                if !before.is_null() {
-                    drop(Weak::<_, R>::from_raw(before));
+                if ptr::eq(n, before) {
+                    Ok(null_mut())
+                } else {
+                    if !n.is_null() {
+                        Weak::<_, R>::increment_weak_count(n);
+                    }
+                    Ok(before)
                 }
-                Ok(())
             },
             Err(before) => {
                 if before.is_null() {
@@ -248,6 +1061,14 @@ impl<T: 'static, R: Protect + Retire> AtomicWeak<T, R> {
                     unsafe { Err(Some(Weak::<_, R>::clone_from_raw(before))) }
                 }
             }
+        }) {
+            Ok(before) => unsafe {
+                if !before.is_null() {
+                    drop(Weak::<_, R>::from_raw(before));
+                }
+                Ok(())
+            },
+            Err(before) => Err(before),
         }
     }
 
@@ -279,6 +1100,24 @@ impl<T: 'static, R: Protect + Retire> AtomicWeak<T, R> {
         }
     }
 
+    /// Stores `new`'s pointer (or [`None`]) into `self` and returns the previous value as a
+    /// [`Weak`], or [`None`] if it was null. The weak count held by `self` is transferred to the
+    /// returned `Weak`.
+    pub fn swap<N: Shared<T>>(&self, new: Option<&N>, order: Ordering) -> Option<Weak<T, R>> {
+        let ptr: *const T = new.map_or(null(), N::as_ptr);
+        if !ptr.is_null() {
+            unsafe {
+                Weak::<_, R>::increment_weak_count(ptr);
+            }
+        }
+        let before = self.ptr.swap(ptr.cast_mut(), order);
+        if before.is_null() {
+            None
+        } else {
+            unsafe { Some(Weak::<_, R>::from_raw(before)) }
+        }
+    }
+
     /// Returns a [`Strong`] (an [`Arc`] or a [`Snapshot`]) if the strong count is at least one.
     /// Analogous to [`std::sync::Weak::upgrade`].
     pub fn upgrade<V: Strong<T>>(&self, order: Ordering) -> Option<V> {
@@ -311,6 +1150,7 @@ impl<T: 'static, R: Protect + Retire> Clone for AtomicWeak<T, R> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: 'static> Default for AtomicWeak<T, StandardReclaimer> {
     fn default() -> Self {
         Self {
@@ -385,11 +1225,215 @@ impl<T: 'static, R: Protect + ProtectPtr + Retire> From<&Arc<T, R>> for AtomicWe
     }
 }
 
+default_reclaimer! {
+    /// An [`AtomicArc`] that stores a small tag in the unused low bits of its pointer.
+    ///
+    /// Many lock-free algorithms mark pointers, e.g. for logical deletion in a Harris linked list, or
+    /// attach a version to a pointer to avoid the ABA problem. The pointer and the tag are read and
+    /// updated together in a single atomic operation. The tag must fit within
+    /// [`tag_mask`][`AtomicArcTagged::tag_mask`], which is derived from the alignment of the
+    /// allocation (at least 3 bits on 64-bit targets).
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArcTagged, Snapshot};
+    ///
+    /// let arc = Arc::new(53);
+    /// let atomic = AtomicArcTagged::default();
+    /// atomic.store_tagged(Some(&arc), 1, SeqCst);
+    ///
+    /// let (snapshot, tag) = atomic.load_tagged::<Snapshot<_>>(SeqCst);
+    /// assert_eq!((*snapshot.unwrap(), tag), (53, 1));
+    ///
+    /// assert_eq!(atomic.set_tag(2, SeqCst), 1);
+    /// assert!(atomic
+    ///     .compare_exchange_tagged::<_, Arc<_>, Snapshot<_>>((Some(&arc), 2), (None, 0), SeqCst, SeqCst)
+    ///     .is_ok());
+    /// ```
+    pub struct AtomicArcTagged<T: 'static, R: Protect + Retire> {
+        ptr: AtomicPtr<T>,
+        phantom: PhantomData<T>,
+        phantom_r: PhantomData<R>,
+    }
+}
+
+impl<T: 'static, R: Protect + Retire> AtomicArcTagged<T, R> {
+    /// Creates a null `AtomicArcTagged` with a tag of zero. Unlike `Default`, this works with any
+    /// reclaimer.
+    #[cfg(not(loom))]
+    pub const fn null() -> Self {
+        Self {
+            ptr: AtomicPtr::new(null_mut()),
+            phantom: PhantomData,
+            phantom_r: PhantomData,
+        }
+    }
+
+    /// Creates a null `AtomicArcTagged` with a tag of zero. Loom's atomics can't be created in a
+    /// `const fn`.
+    #[cfg(loom)]
+    pub fn null() -> Self {
+        Self {
+            ptr: AtomicPtr::new(null_mut()),
+            phantom: PhantomData,
+            phantom_r: PhantomData,
+        }
+    }
+
+    /// Returns the bits that are available for the tag.
+    pub const fn tag_mask() -> usize {
+        mem::align_of::<ArcInner<T>>() - 1
+    }
+
+    /// Stores `new`'s pointer and `new_tag` into `self` if `self` contains `current`'s pointer and
+    /// `current_tag`. On failure, the observed value and tag are returned.
+    ///
+    /// # Panics
+    /// Panics if either tag does not fit within [`tag_mask`][`AtomicArcTagged::tag_mask`].
+    #[allow(clippy::type_complexity)]
+    pub fn compare_exchange_tagged<C, N, V>(
+        &self,
+        current: (Option<&C>, usize),
+        new: (Option<&N>, usize),
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(), (Option<V>, usize)>
+    where
+        C: Strong<T>,
+        N: Strong<T>,
+        V: Strong<T>,
+    {
+        let c = Self::compose(current.0.map_or(null(), C::as_ptr), current.1);
+        let n: *const T = new.0.map_or(null(), N::as_ptr);
+        let n_tagged = Self::compose(n, new.1);
+        match with_critical_section::<R, _, _>(|| {
+            match self.ptr.compare_exchange(c, n_tagged, success, failure) {
+                Ok(before) => {
+                    if !n.is_null() {
+                        unsafe {
+                            Arc::<_, R>::increment_strong_count(n);
+                        }
+                    }
+                    Ok(Self::decompose(before).0)
+                }
+                Err(before) => {
+                    let (ptr, tag) = Self::decompose(before);
+                    let value = (!ptr.is_null()).then(|| unsafe { V::clone_from_raw(ptr) });
+                    Err((value, tag))
+                }
+            }
+        }) {
+            Ok(before) => {
+                if !before.is_null() {
+                    unsafe {
+                        drop(Arc::<_, R>::from_raw(before));
+                    }
+                }
+                Ok(())
+            }
+            Err(before) => Err(before),
+        }
+    }
+
+    /// Loads the pointer and the tag. The pointer is returned as the desired type (`Arc` or
+    /// `Snapshot`), or [`None`] if it is null.
+    pub fn load_tagged<V: Strong<T>>(&self, order: Ordering) -> (Option<V>, usize) {
+        with_critical_section::<R, _, _>(|| {
+            let (ptr, tag) = Self::decompose(self.ptr.load(order));
+            let value = (!ptr.is_null()).then(|| unsafe { V::clone_from_raw(ptr) });
+            (value, tag)
+        })
+    }
+
+    /// Loads only the tag. This does not protect the pointer.
+    pub fn load_tag(&self, order: Ordering) -> usize {
+        Self::decompose(self.ptr.load(order)).1
+    }
+
+    /// Replaces the tag, leaving the pointer unchanged, and returns the previous tag.
+    ///
+    /// # Panics
+    /// Panics if `tag` does not fit within [`tag_mask`][`AtomicArcTagged::tag_mask`].
+    pub fn set_tag(&self, tag: usize, order: Ordering) -> usize {
+        let mut before = self.ptr.load(Relaxed);
+        loop {
+            let after = Self::compose(Self::decompose(before).0, tag);
+            match self
+                .ptr
+                .compare_exchange_weak(before, after, order, Relaxed)
+            {
+                Ok(_) => return Self::decompose(before).1,
+                Err(actual) => before = actual,
+            }
+        }
+    }
+
+    /// Stores `new`'s pointer (or [`None`]) and `tag` into `self`.
+    ///
+    /// # Panics
+    /// Panics if `tag` does not fit within [`tag_mask`][`AtomicArcTagged::tag_mask`].
+    pub fn store_tagged<N: Strong<T>>(&self, new: Option<&N>, tag: usize, order: Ordering) {
+        let ptr: *const T = new.map_or(null(), N::as_ptr);
+        let tagged = Self::compose(ptr, tag);
+        if !ptr.is_null() {
+            unsafe {
+                Arc::<_, R>::increment_strong_count(ptr);
+            }
+        }
+        let (before, _) = Self::decompose(self.ptr.swap(tagged, order));
+        if !before.is_null() {
+            unsafe {
+                drop(Arc::<_, R>::from_raw(before));
+            }
+        }
+    }
+
+    fn compose(ptr: *const T, tag: usize) -> *mut T {
+        assert_eq!(
+            tag & !Self::tag_mask(),
+            0,
+            "the tag does not fit in the pointer"
+        );
+        ptr.cast_mut().map_addr(|addr| addr | tag)
+    }
+
+    fn decompose(tagged: *mut T) -> (*mut T, usize) {
+        (
+            tagged.map_addr(|addr| addr & !Self::tag_mask()),
+            tagged.addr() & Self::tag_mask(),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static> Default for AtomicArcTagged<T, StandardReclaimer> {
+    /// Creates a null `AtomicArcTagged` with a tag of zero.
+    fn default() -> Self {
+        Self {
+            ptr: AtomicPtr::default(),
+            phantom: PhantomData,
+            phantom_r: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static, R: Protect + Retire> Drop for AtomicArcTagged<T, R> {
+    fn drop(&mut self) {
+        let (ptr, _) = Self::decompose(self.ptr.load(SeqCst));
+        if !ptr.is_null() {
+            unsafe {
+                drop(Arc::<_, R>::from_raw(ptr));
+            }
+        }
+    }
+}
+
 fn with_critical_section<R: Protect, V, F: Fn() -> V>(f: F) -> V {
-    R::begin_critical_section();
-    let result = f();
-    R::end_critical_section();
-    result
+    // The critical section must end even if `f` panics (e.g. due to an invalid ordering), or
+    // this thread would block reclamation indefinitely.
+    let _critical_section = CriticalSection::<R>::enter_in();
+    f()
 }
 
 /// A marker trait for pointers that prevent deallocation of an object. Implemented by [`Arc`] and
@@ -402,3 +1446,403 @@ impl<T, X> Strong<T> for X where X: Shared<T> + TryCloneFromRaw<T> {}
 pub trait Shared<T>: AsPtr<T> + CloneFromRaw<T> {}
 
 impl<T, X> Shared<T> for X where X: AsPtr<T> + CloneFromRaw<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::smr::standard_reclaimer::IsolatedReclaimer;
+    use crate::{
+        Arc, AsPtr, AtomicArc, AtomicArcArray, AtomicArcTagged, AtomicWeak, Snapshot,
+        TransferError, Weak,
+    };
+    use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release, SeqCst};
+
+    #[test]
+    fn test_atomic_weak_compare_exchange_back_pointers() {
+        #[derive(Default)]
+        struct Node {
+            prev: AtomicWeak<Self>,
+            next: AtomicArc<Self>,
+        }
+
+        let a = Arc::new(Node::default());
+        let b = Arc::new(Node::default());
+        let c = Arc::new(Node::default());
+
+        // Link a <-> b.
+        a.next.store(Some(&b), SeqCst);
+        assert!(b
+            .prev
+            .compare_exchange(None::<&Weak<_>>, Some(&a), SeqCst, SeqCst)
+            .is_ok());
+        assert_eq!(Arc::weak_count(&a), 1);
+
+        // Insert c between a and b, swinging b's back-pointer from a to c.
+        c.next.store(Some(&b), SeqCst);
+        c.prev.store(Some(&a), SeqCst);
+        a.next.store(Some(&c), SeqCst);
+        let weak_a = Arc::downgrade(&a);
+        assert!(b
+            .prev
+            .compare_exchange(Some(&weak_a), Some(&c), SeqCst, SeqCst)
+            .is_ok());
+        let actual = b
+            .prev
+            .compare_exchange(Some(&weak_a), Some(&c), SeqCst, SeqCst)
+            .unwrap_err()
+            .unwrap();
+        assert!(std::ptr::eq(Weak::as_ptr(&actual), Arc::as_ptr(&c)));
+        assert_eq!(Arc::weak_count(&c), 2);
+
+        // The back-pointers must not keep the nodes alive.
+        drop(a);
+        assert!(weak_a.upgrade().is_none());
+        assert!(c.prev.upgrade::<Arc<_>>(SeqCst).is_none());
+        // A failed upgrade must leave the strong count untouched.
+        assert_eq!(weak_a.strong_count(), 0);
+    }
+
+    #[test]
+    fn test_atomic_weak_swap() {
+        let a = Arc::new(1);
+        let b = Arc::new(2);
+        let atomic = AtomicWeak::from(&a);
+        assert_eq!(Arc::weak_count(&a), 1);
+
+        let before = atomic.swap(Some(&b), SeqCst).unwrap();
+        assert_eq!(*before.upgrade().unwrap(), 1);
+        assert_eq!(Arc::weak_count(&a), 1);
+        assert_eq!(Arc::weak_count(&b), 1);
+
+        drop(before);
+        assert_eq!(Arc::weak_count(&a), 0);
+
+        let before = atomic.swap(None::<&Arc<_>>, SeqCst).unwrap();
+        assert!(std::ptr::eq(Weak::as_ptr(&before), Arc::as_ptr(&b)));
+        assert!(atomic.swap(None::<&Arc<_>>, SeqCst).is_none());
+    }
+
+    #[test]
+    fn test_atomic_weak_compare_exchange_dead_entry() {
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker>;
+        let a = Arc::<_, Reclaimer>::new_in(1);
+        let b = Arc::<_, Reclaimer>::new_in(2);
+        let atomic = AtomicWeak::from(&a);
+        let expected = atomic.load(SeqCst).unwrap();
+        // Once the value is reclaimed, only the explicit weak references remain.
+        drop(a);
+        Reclaimer::flush();
+        assert!(expected.upgrade().is_none());
+        assert_eq!(expected.weak_count(), 2);
+
+        // A mismatch returns the entry that was observed, even though it is dead.
+        let other = Arc::downgrade(&b);
+        let actual = atomic
+            .compare_exchange(Some(&other), Some(&b), SeqCst, SeqCst)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(Weak::as_ptr(&actual), Weak::as_ptr(&expected));
+        assert_eq!(expected.weak_count(), 3);
+        drop(actual);
+
+        // The dead entry is evicted by identity.
+        assert!(atomic
+            .compare_exchange(Some(&expected), Some(&b), SeqCst, SeqCst)
+            .is_ok());
+        assert_eq!(expected.weak_count(), 1);
+        assert_eq!(Arc::weak_count(&b), 2);
+        assert_eq!(*atomic.upgrade::<Arc<_, Reclaimer>>(SeqCst).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_atomic_arc_swap() {
+        let atomic = AtomicArc::new(Some(1));
+        let b = Arc::new(2);
+
+        let before = atomic.swap(Some(&b), Acquire).unwrap();
+        assert_eq!(*before, 1);
+        assert_eq!(Arc::strong_count(&before), 1);
+        assert_eq!(Arc::strong_count(&b), 2);
+
+        let before = atomic.swap(None::<&Arc<_>>, AcqRel).unwrap();
+        assert!(Arc::ptr_eq(&before, &b));
+        assert!(atomic.swap(None::<&Arc<_>>, Relaxed).is_none());
+    }
+
+    #[test]
+    fn test_atomic_arc_compare_exchange_snapshot_as_new() {
+        let source = AtomicArc::new(Some(1));
+        let target = AtomicArc::new(Some(2));
+        let snapshot = source.load::<Snapshot<_>>(SeqCst).unwrap();
+        let current = target.load::<Snapshot<_>>(SeqCst);
+
+        let result = target.compare_exchange::<_, _, Snapshot<_>>(
+            current.as_ref(),
+            Some(&snapshot),
+            SeqCst,
+            SeqCst,
+        );
+        assert!(result.is_ok());
+        let installed = target.load::<Arc<_>>(SeqCst).unwrap();
+        assert!(std::ptr::eq(
+            Arc::as_ptr(&installed),
+            Snapshot::as_ptr(&snapshot)
+        ));
+        // One count for each AtomicArc and one for `installed`.
+        assert_eq!(Arc::strong_count(&installed), 3);
+
+        // A failed exchange leaves the count unchanged.
+        let result = target.compare_exchange::<_, _, Snapshot<_>>(
+            current.as_ref(),
+            Some(&snapshot),
+            SeqCst,
+            SeqCst,
+        );
+        assert!(result.is_err());
+        assert_eq!(Arc::strong_count(&installed), 3);
+    }
+
+    #[test]
+    fn test_atomic_arc_compare_exchange_owned() {
+        let atomic = AtomicArc::new(Some(1));
+        let x = Arc::new(2);
+
+        let (actual, unused) = atomic
+            .compare_exchange_owned::<Arc<_>, Arc<_>>(None, Some(x.clone()), SeqCst, SeqCst)
+            .unwrap_err();
+        assert_eq!(actual.as_deref(), Some(&1));
+        assert!(Arc::ptr_eq(unused.as_ref().unwrap(), &x));
+        assert_eq!(Arc::strong_count(&x), 2);
+
+        assert_eq!(Arc::strong_count(actual.as_ref().unwrap()), 2);
+        assert!(atomic
+            .compare_exchange_owned::<_, Arc<_>>(actual.as_ref(), unused, SeqCst, SeqCst)
+            .is_ok());
+        // The count of the consumed `Arc` now belongs to `atomic`, and the one `atomic` held on
+        // the displaced value is released.
+        assert_eq!(Arc::strong_count(&x), 2);
+        assert_eq!(Arc::strong_count(actual.as_ref().unwrap()), 1);
+        assert!(Arc::ptr_eq(&atomic.load::<Arc<_>>(SeqCst).unwrap(), &x));
+    }
+
+    #[test]
+    fn test_atomic_arc_set_if_none() {
+        let atomic = AtomicArc::default();
+        let winners: Vec<_> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let atomic = &atomic;
+                    s.spawn(move || {
+                        let new = Arc::new(i);
+                        match atomic.set_if_none::<_, Arc<_>>(&new, SeqCst, SeqCst) {
+                            Ok(()) => new,
+                            Err(winner) => winner,
+                        }
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        // Every thread sees the value that was installed first.
+        let installed = atomic.load::<Arc<_>>(SeqCst).unwrap();
+        assert!(winners.iter().all(|w| Arc::ptr_eq(w, &installed)));
+        assert_eq!(Arc::strong_count(&installed), 6);
+    }
+
+    #[test]
+    fn test_atomic_arc_take() {
+        let b = Arc::new(2);
+        let atomic = AtomicArc::from(&b);
+        assert_eq!(Arc::strong_count(&b), 2);
+
+        let before = atomic.take(SeqCst).unwrap();
+        assert!(Arc::ptr_eq(&before, &b));
+        assert_eq!(Arc::strong_count(&b), 2);
+        assert!(atomic.is_none(SeqCst));
+        assert!(atomic.take(SeqCst).is_none());
+    }
+
+    #[test]
+    #[cfg(not(loom))]
+    fn test_atomic_arc_get_mut() {
+        let a = Arc::new(1);
+        let b = Arc::new(2);
+        let mut atomic = AtomicArc::from(&a);
+        assert!(Arc::get_mut(atomic.get_mut().unwrap()).is_none());
+
+        // The replaced `Arc` releases the count that `atomic` held.
+        *atomic.get_mut().unwrap() = b.clone();
+        assert_eq!(Arc::strong_count(&a), 1);
+        assert_eq!(Arc::strong_count(&b), 2);
+        assert!(Arc::ptr_eq(&atomic.load::<Arc<_>>(SeqCst).unwrap(), &b));
+
+        drop(b);
+        *Arc::get_mut(atomic.get_mut().unwrap()).unwrap() += 1;
+        assert_eq!(*atomic.into_inner().unwrap(), 3);
+        assert!(AtomicArc::<i32>::default().get_mut().is_none());
+    }
+
+    #[test]
+    fn test_atomic_arc_array() {
+        let a = Arc::new(1);
+        let b = Arc::new(2);
+        let array = AtomicArcArray::<_, 3>::default();
+        array.store(0, Some(&a), SeqCst);
+        array.store(2, Some(&a), SeqCst);
+        assert_eq!(Arc::strong_count(&a), 3);
+
+        // Each element is compared independently.
+        assert!(array
+            .compare_exchange::<_, _, Arc<_>>(1, Some(&a), Some(&b), SeqCst, SeqCst)
+            .is_err());
+        assert!(array
+            .compare_exchange::<_, _, Arc<_>>(2, Some(&a), Some(&b), SeqCst, SeqCst)
+            .is_ok());
+        assert_eq!(*array.load::<Snapshot<_>>(0, SeqCst).unwrap(), 1);
+        assert!(array.load::<Snapshot<_>>(1, SeqCst).is_none());
+        assert_eq!(*array.load::<Snapshot<_>>(2, SeqCst).unwrap(), 2);
+        assert_eq!((Arc::strong_count(&a), Arc::strong_count(&b)), (2, 2));
+
+        drop(array);
+        assert_eq!((Arc::strong_count(&a), Arc::strong_count(&b)), (1, 1));
+    }
+
+    #[test]
+    fn test_atomic_arc_fetch_store() {
+        let atomic = AtomicArc::new(Some(String::new()));
+        let snapshot = atomic.fetch_store::<_, Snapshot<_>>(&Arc::new(String::from("a")), SeqCst);
+        // The new value is replaced and its last `Arc` is dropped, but the snapshot keeps it alive.
+        atomic.store::<Arc<_>>(None, SeqCst);
+        assert_eq!(*snapshot, "a");
+
+        let arc = atomic.fetch_store::<_, Arc<_>>(&Arc::new(String::from("b")), SeqCst);
+        assert_eq!(*arc, "b");
+        assert_eq!(Arc::strong_count(&arc), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_atomic_arc_load_release() {
+        AtomicArc::new(Some(1)).load::<Arc<_>>(Release);
+    }
+
+    #[test]
+    fn test_atomic_arc_transfer() {
+        let (x, y) = (Arc::new(1), Arc::new(2));
+        let from = AtomicArc::from(&y);
+        let to = AtomicArc::default();
+
+        // `from` holds `y`, so `to` is restored.
+        assert_eq!(
+            AtomicArc::transfer(&from, &to, Some(&x), None::<&Arc<_>>),
+            Err(TransferError::From)
+        );
+        assert!(to.is_none(SeqCst));
+        assert!(from
+            .load::<Snapshot<_>>(SeqCst)
+            .is_some_and(|s| Snapshot::ptr_eq(&s, &y)));
+
+        let y_snapshot = from.load::<Snapshot<_>>(SeqCst).unwrap();
+        assert_eq!(
+            AtomicArc::transfer(&from, &to, Some(&y_snapshot), None::<&Arc<_>>),
+            Ok(())
+        );
+        assert!(from.is_none(SeqCst));
+        assert_eq!(*to.load::<Arc<_>>(SeqCst).unwrap(), 2);
+        assert_eq!(
+            AtomicArc::transfer(&to, &from, Some(&y), Some(&x)),
+            Err(TransferError::To)
+        );
+        assert_eq!(*to.load::<Arc<_>>(SeqCst).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_atomic_arc_compare_exchange_orderings() {
+        let atomic = AtomicArc::new(Some(1));
+        let current = atomic.load::<Arc<_>>(Acquire);
+        let new = Arc::new(2);
+        assert!(atomic
+            .compare_exchange::<_, _, Arc<_>>(current.as_ref(), Some(&new), Release, Acquire)
+            .is_ok());
+        assert!(atomic
+            .compare_exchange_weak::<_, _, Arc<_>>(current.as_ref(), Some(&new), AcqRel, Relaxed)
+            .is_err());
+        assert!(atomic
+            .compare_exchange_raw(current.as_ref(), Some(&new), Relaxed, Relaxed)
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_atomic_arc_compare_exchange_release_failure() {
+        let atomic = AtomicArc::new(Some(1));
+        let current = atomic.load::<Arc<_>>(SeqCst);
+        let _ =
+            atomic.compare_exchange::<_, Arc<_>, Arc<_>>(current.as_ref(), None, SeqCst, Release);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_atomic_arc_compare_exchange_weak_acq_rel_failure() {
+        let atomic = AtomicArc::new(Some(1));
+        let _ = atomic.compare_exchange_weak::<Arc<_>, Arc<_>, Arc<_>>(None, None, SeqCst, AcqRel);
+    }
+
+    #[test]
+    fn test_debug() {
+        let atomic = AtomicArc::new(Some(53));
+        assert_eq!(format!("{atomic:?}"), "Some(53)");
+        let arc = atomic.load::<Arc<_>>(SeqCst).unwrap();
+        assert_eq!(format!("{arc:?}"), "53");
+        assert_eq!(format!("{:?}", Snapshot::from(&arc)), "53");
+        assert_eq!(format!("{:?}", Arc::downgrade(&arc)), "(Weak)");
+        atomic.store(None::<&Arc<_>>, SeqCst);
+        assert_eq!(format!("{atomic:?}"), "None");
+    }
+
+    #[test]
+    fn test_atomic_arc_tagged() {
+        let a = Arc::new(1);
+        let b = Arc::new(2);
+        let atomic = AtomicArcTagged::default();
+        assert!(AtomicArcTagged::<usize>::tag_mask() >= 1);
+        atomic.store_tagged(Some(&a), 1, SeqCst);
+        assert_eq!(Arc::strong_count(&a), 2);
+
+        // A matching pointer with a stale tag must fail and report the observed tag.
+        let (observed, tag) = atomic
+            .compare_exchange_tagged::<_, _, Arc<_>>((Some(&a), 0), (Some(&b), 0), SeqCst, SeqCst)
+            .unwrap_err();
+        assert!(Arc::ptr_eq(&observed.unwrap(), &a));
+        assert_eq!(tag, 1);
+
+        assert!(atomic
+            .compare_exchange_tagged::<_, _, Arc<_>>((Some(&a), 1), (Some(&b), 0), SeqCst, SeqCst)
+            .is_ok());
+        assert_eq!((Arc::strong_count(&a), Arc::strong_count(&b)), (1, 2));
+        assert_eq!(atomic.set_tag(1, SeqCst), 0);
+        assert_eq!(*atomic.load_tagged::<Snapshot<_>>(SeqCst).0.unwrap(), 2);
+        assert_eq!(atomic.load_tag(SeqCst), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_atomic_arc_tagged_oversized_tag() {
+        let atomic = AtomicArcTagged::<usize>::default();
+        atomic.set_tag(AtomicArcTagged::<usize>::tag_mask() + 1, SeqCst);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_atomic_arc_serde() {
+        let atomic = AtomicArc::new(Some(53));
+        assert_eq!(serde_json::to_string(&atomic).unwrap(), "53");
+        atomic.store::<Arc<_>>(None, SeqCst);
+        assert_eq!(serde_json::to_string(&atomic).unwrap(), "null");
+
+        let atomic: AtomicArc<String> = serde_json::from_str("\"aarc\"").unwrap();
+        assert_eq!(*atomic.load::<Snapshot<_>>(SeqCst).unwrap(), "aarc");
+        let atomic: AtomicArc<String> = serde_json::from_str("null").unwrap();
+        assert!(atomic.is_none(SeqCst));
+    }
+}
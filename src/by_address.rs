@@ -0,0 +1,103 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// A wrapper that compares, orders, and hashes a pointer (e.g. an [`Arc`] or a [`Snapshot`]) by
+/// the address that it points to, rather than by value.
+///
+/// The comparison traits on [`Arc`] and [`Snapshot`] forward to `T`, like those of
+/// [`std::sync::Arc`]. Wrapping them in `ByAddress` opts into identity semantics instead, which
+/// is what interning tables and visited-sets typically want.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+/// use aarc::{Arc, ByAddress};
+///
+/// let x = Arc::new(53);
+/// let mut set = HashSet::new();
+/// assert!(set.insert(ByAddress(x.clone())));
+/// assert!(!set.insert(ByAddress(x.clone())));
+/// // An equal value in a different allocation is a different key.
+/// assert!(set.insert(ByAddress(Arc::new(53))));
+/// ```
+///
+/// [`Arc`]: `crate::Arc`
+/// [`Snapshot`]: `crate::Snapshot`
+#[derive(Clone, Copy, Default)]
+pub struct ByAddress<P>(pub P);
+
+impl<P: Deref> ByAddress<P> {
+    fn addr(&self) -> *const () {
+        (&*self.0 as *const P::Target).cast()
+    }
+}
+
+impl<P: Deref> Deref for ByAddress<P> {
+    type Target = P;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P: Deref> From<P> for ByAddress<P> {
+    fn from(value: P) -> Self {
+        Self(value)
+    }
+}
+
+impl<P: Deref + Debug> Debug for ByAddress<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "ByAddress({:?} @ {:p})", self.0, self.addr())
+    }
+}
+
+impl<P: Deref> PartialEq for ByAddress<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl<P: Deref> Eq for ByAddress<P> {}
+
+impl<P: Deref> PartialOrd for ByAddress<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Deref> Ord for ByAddress<P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl<P: Deref> Hash for ByAddress<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, AtomicArc, ByAddress, Snapshot};
+    use std::collections::HashSet;
+    use std::sync::atomic::Ordering::SeqCst;
+
+    #[test]
+    fn test_by_address_identity() {
+        let x = Arc::new(53);
+        let y = Arc::new(53);
+        assert_eq!(x, y);
+        assert_ne!(ByAddress(x.clone()), ByAddress(y.clone()));
+
+        let atomic = AtomicArc::from(&x);
+        let snapshot = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+        assert_eq!(ByAddress(snapshot.clone()), ByAddress(snapshot));
+
+        let set: HashSet<_> = [x.clone(), y, x].into_iter().map(ByAddress).collect();
+        assert_eq!(set.len(), 2);
+    }
+}
@@ -0,0 +1,509 @@
+use crate::smr::drc::{Protect, ProtectPtr, Retire};
+#[cfg(feature = "std")]
+use crate::smr::standard_reclaimer::StandardReclaimer;
+use crate::utils::sync::AtomicIsize;
+use crate::{Arc, AtomicArc, AtomicArcTagged, Snapshot};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{BuildHasher, Hash};
+use core::ops::Deref;
+use core::sync::atomic::Ordering::SeqCst;
+#[cfg(feature = "std")]
+use std::hash::RandomState;
+
+default_reclaimer! {
+    /// A lock-free concurrent hash map.
+    ///
+    /// Each bucket is an [`AtomicArc`] to an immutable chain of nodes. Updates copy the nodes that
+    /// precede the affected entry and swap in the new chain with a single CAS, sharing the rest of
+    /// the old chain, so readers always observe a whole bucket from before or after an update.
+    /// Unlinked nodes are reclaimed like any other [`Arc`] once the last reader releases them.
+    ///
+    /// The map doubles its number of buckets once it holds more entries than buckets. The buckets
+    /// of the old table are frozen one by one and their entries copied to the new table, which
+    /// replaces the old one once every bucket has been copied. Any thread that tries to update a
+    /// frozen bucket helps to finish the resize first, and lookups in a frozen bucket continue in
+    /// the new table, so no operation waits for another.
+    ///
+    /// Lookups return a [`Snapshot`] of the [`HashMapEntry`], which dereferences to the value and
+    /// remains valid even if the entry is removed or replaced in the meantime.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::collections::HashMap;
+    ///
+    /// let map = HashMap::new();
+    /// assert!(map.insert("a", 1).is_none());
+    /// assert!(map.insert("b", 2).is_none());
+    /// assert_eq!(**map.insert("a", 3).unwrap(), 1);
+    ///
+    /// let entry = map.get("a").unwrap();
+    /// assert_eq!((*entry.key(), **entry), ("a", 3));
+    ///
+    /// assert_eq!(**map.remove("b").unwrap(), 2);
+    /// assert!(!map.contains_key("b"));
+    /// ```
+    pub struct HashMap<
+        K: 'static,
+        V: 'static,
+        S: 'static = RandomState,
+        R: Protect + ProtectPtr + Retire + 'static
+    > {
+        table: AtomicArc<Table<K, V, R>, R>,
+        // The number of entries. It may briefly be negative if an entry is removed before its
+        // insertion has been counted.
+        len: AtomicIsize,
+        hasher: S,
+    }
+}
+
+/// A key-value pair in a [`HashMap`], which dereferences to its value.
+pub struct HashMapEntry<K, V> {
+    key: K,
+    value: V,
+}
+
+struct HashMapNode<K: 'static, V: 'static, R: Retire + 'static> {
+    entry: Arc<HashMapEntry<K, V>, R>,
+    next: Option<Arc<Self, R>>,
+}
+
+type Bucket<K, V, R> = AtomicArcTagged<HashMapNode<K, V, R>, R>;
+
+type Chain<K, V, R> = Option<Arc<HashMapNode<K, V, R>, R>>;
+
+struct Table<K: 'static, V: 'static, R: Protect + Retire + 'static> {
+    buckets: Box<[Bucket<K, V, R>]>,
+    // The table with twice as many buckets that replaces this one, once a resize has begun.
+    next: AtomicArc<Self, R>,
+}
+
+/// The tag of a bucket whose entries have been copied to the next table. It is never updated
+/// again.
+const FROZEN: usize = 1;
+/// The tag of a bucket that has not received its entries from the previous table yet.
+const UNFILLED: usize = 2;
+
+/// The number of buckets used by [`HashMap::new`].
+#[cfg(feature = "std")]
+const DEFAULT_BUCKETS: usize = 64;
+
+#[cfg(feature = "std")]
+impl<K: 'static, V: 'static> HashMap<K, V, RandomState, StandardReclaimer> {
+    pub fn new() -> Self {
+        Self::with_buckets(DEFAULT_BUCKETS)
+    }
+
+    /// Creates an empty map with `buckets` buckets initially.
+    ///
+    /// # Panics
+    /// Panics if `buckets` is zero.
+    pub fn with_buckets(buckets: usize) -> Self {
+        Self::with_hasher_in(buckets, RandomState::new())
+    }
+}
+
+impl<K: 'static, V: 'static, S: 'static, R: Protect + ProtectPtr + Retire + 'static>
+    HashMap<K, V, S, R>
+{
+    /// Creates an empty map with `buckets` buckets initially, which hashes keys with `hasher`.
+    ///
+    /// # Panics
+    /// Panics if `buckets` is zero.
+    pub fn with_hasher_in(buckets: usize, hasher: S) -> Self {
+        assert!(buckets > 0, "a hash map needs at least one bucket");
+        let table = Table {
+            buckets: (0..buckets).map(|_| AtomicArcTagged::null()).collect(),
+            next: AtomicArc::null(),
+        };
+        Self {
+            table: AtomicArc::from(&Arc::<_, R>::new_in(table)),
+            len: AtomicIsize::new(0),
+            hasher,
+        }
+    }
+}
+
+impl<K, V, S, R> HashMap<K, V, S, R>
+where
+    K: 'static + Hash + Eq,
+    V: 'static,
+    S: 'static + BuildHasher,
+    R: Protect + ProtectPtr + Retire + 'static,
+{
+    /// Inserts a key-value pair, returning the entry it replaced, if any.
+    pub fn insert(&self, key: K, value: V) -> Option<Arc<HashMapEntry<K, V>, R>> {
+        let hash = self.hasher.hash_one(&key);
+        let entry = Arc::<_, R>::new_in(HashMapEntry { key, value });
+        let (table, replaced) = self.update(hash, |head| {
+            let (next, replaced) = match Self::unlink(head.map(Deref::deref), &entry.key) {
+                Some((rest, old)) => (rest, Some(old.clone())),
+                None => (head.cloned(), None),
+            };
+            let node = Arc::<_, R>::new_in(HashMapNode {
+                entry: entry.clone(),
+                next,
+            });
+            Some((Some(node), replaced))
+        })?;
+        if replaced.is_none() {
+            let len = self.len.fetch_add(1, SeqCst) + 1;
+            if len > table.buckets.len() as isize {
+                self.resize(&table);
+            }
+        }
+        replaced
+    }
+
+    /// Removes the entry for `key` and returns it, or [`None`] if the map does not contain `key`.
+    pub fn remove<Q>(&self, key: &Q) -> Option<Arc<HashMapEntry<K, V>, R>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let (_, removed) = self.update(self.hasher.hash_one(key), |head| {
+            let (rest, old) = Self::unlink(head.map(Deref::deref), key)?;
+            Some((rest, old.clone()))
+        })?;
+        self.len.fetch_sub(1, SeqCst);
+        Some(removed)
+    }
+
+    /// Returns a [`Snapshot`] of the entry for `key`, or [`None`] if the map does not contain it.
+    pub fn get<Q>(&self, key: &Q) -> Option<Snapshot<HashMapEntry<K, V>, R>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let hash = self.hasher.hash_one(key);
+        let mut table = self.table.load::<Snapshot<_, R>>(SeqCst).unwrap();
+        let head = loop {
+            let (head, tag) =
+                table.buckets[Self::index(hash, &table)].load_tagged::<Snapshot<_, R>>(SeqCst);
+            if tag & FROZEN == 0 {
+                break head;
+            }
+            // The entries may have been updated in the next table since they were copied, unless
+            // they haven't been copied yet.
+            let next = table.next.load::<Snapshot<_, R>>(SeqCst).unwrap();
+            if next.buckets[Self::index(hash, &next)].load_tag(SeqCst) & UNFILLED != 0 {
+                break head;
+            }
+            table = next;
+        };
+        // The snapshot of the head keeps the whole chain alive.
+        let head = head?;
+        let mut curr: &HashMapNode<K, V, R> = &head;
+        loop {
+            if curr.entry.key.borrow() == key {
+                return Some(Snapshot::from(&curr.entry));
+            }
+            curr = curr.next.as_deref()?;
+        }
+    }
+
+    /// Returns `true` if the map contains an entry for `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.get(key).is_some()
+    }
+
+    fn index(hash: u64, table: &Table<K, V, R>) -> usize {
+        (hash % table.buckets.len() as u64) as usize
+    }
+
+    /// Replaces the chain of the bucket for `hash` in the current table with the one returned by
+    /// `f`, along with a value to return, retrying with the new chain until the replacement
+    /// succeeds. Returns [`None`] without updating the bucket if `f` does.
+    #[allow(clippy::type_complexity)]
+    fn update<T, F>(&self, hash: u64, mut f: F) -> Option<(Snapshot<Table<K, V, R>, R>, T)>
+    where
+        F: FnMut(Option<&Arc<HashMapNode<K, V, R>, R>>) -> Option<(Chain<K, V, R>, T)>,
+    {
+        loop {
+            // Every bucket of the current table has been filled.
+            let table = self.table.load::<Snapshot<_, R>>(SeqCst).unwrap();
+            let bucket = &table.buckets[Self::index(hash, &table)];
+            // The head is loaded as an `Arc` rather than a `Snapshot` because the new chain may
+            // link to it.
+            let mut current = bucket.load_tagged::<Arc<_, R>>(SeqCst);
+            while current.1 & FROZEN == 0 {
+                let (chain, value) = f(current.0.as_ref())?;
+                match bucket.compare_exchange_tagged(
+                    (current.0.as_ref(), 0),
+                    (chain.as_ref(), 0),
+                    SeqCst,
+                    SeqCst,
+                ) {
+                    Ok(()) => return Some((table, value)),
+                    Err(actual) => current = actual,
+                }
+            }
+            self.finish_resize(&table);
+        }
+    }
+
+    /// Begins moving the entries of `table` to a table with twice as many buckets, unless another
+    /// thread already has, and finishes doing so.
+    fn resize(&self, table: &Snapshot<Table<K, V, R>, R>) {
+        if table.next.is_none(SeqCst) {
+            let buckets = (0..table.buckets.len() * 2)
+                .map(|_| {
+                    let bucket = AtomicArcTagged::null();
+                    bucket.set_tag(UNFILLED, SeqCst);
+                    bucket
+                })
+                .collect();
+            let next = Arc::<_, R>::new_in(Table {
+                buckets,
+                next: AtomicArc::null(),
+            });
+            _ = table.next.compare_exchange::<Arc<_, R>, _, Snapshot<_, R>>(
+                None,
+                Some(&next),
+                SeqCst,
+                SeqCst,
+            );
+        }
+        self.finish_resize(table);
+    }
+
+    /// Copies the entries of `table` to the next table, unless another thread already has, and
+    /// makes the next table current.
+    fn finish_resize(&self, table: &Snapshot<Table<K, V, R>, R>) {
+        let next = table.next.load::<Snapshot<_, R>>(SeqCst).unwrap();
+        // Each bucket is frozen before it is copied, so that the copy can't miss an update.
+        for bucket in table.buckets.iter() {
+            bucket.set_tag(FROZEN, SeqCst);
+        }
+        // Bucket `i` of the next table receives the entries of bucket `i % n` of this table whose
+        // hash is `i` modulo `2 * n`, where `n` is the number of buckets of this table.
+        for (i, bucket) in next.buckets.iter().enumerate() {
+            if bucket.load_tag(SeqCst) & UNFILLED == 0 {
+                continue;
+            }
+            let (head, _) =
+                table.buckets[i % table.buckets.len()].load_tagged::<Snapshot<_, R>>(SeqCst);
+            let mut entries = Vec::new();
+            let mut curr = head.as_deref();
+            while let Some(node) = curr {
+                if Self::index(self.hasher.hash_one(&node.entry.key), &next) == i {
+                    entries.push(&node.entry);
+                }
+                curr = node.next.as_deref();
+            }
+            let chain = entries.into_iter().rev().fold(None, |next, entry| {
+                Some(Arc::<_, R>::new_in(HashMapNode {
+                    entry: entry.clone(),
+                    next,
+                }))
+            });
+            // Only the first thread to copy the bucket succeeds, so later updates aren't undone.
+            _ = bucket.compare_exchange_tagged::<Arc<_, R>, _, Snapshot<_, R>>(
+                (None, UNFILLED),
+                (chain.as_ref(), 0),
+                SeqCst,
+                SeqCst,
+            );
+        }
+        _ = self.table.compare_exchange::<_, _, Snapshot<_, R>>(
+            Some(table),
+            Some(&next),
+            SeqCst,
+            SeqCst,
+        );
+    }
+
+    /// Returns a copy of the chain starting at `head` without the entry for `key`, along with that
+    /// entry, or [`None`] if the chain does not contain `key`. Only the nodes preceding the entry
+    /// are copied; the copy shares the rest of the chain.
+    #[allow(clippy::type_complexity)]
+    fn unlink<'a, Q>(
+        head: Option<&'a HashMapNode<K, V, R>>,
+        key: &Q,
+    ) -> Option<(Chain<K, V, R>, &'a Arc<HashMapEntry<K, V>, R>)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        let mut preceding = Vec::new();
+        let mut curr = head?;
+        while curr.entry.key.borrow() != key {
+            preceding.push(&curr.entry);
+            curr = curr.next.as_deref()?;
+        }
+        let rest = preceding
+            .into_iter()
+            .rev()
+            .fold(curr.next.clone(), |next, entry| {
+                Some(Arc::<_, R>::new_in(HashMapNode {
+                    entry: entry.clone(),
+                    next,
+                }))
+            });
+        Some((rest, &curr.entry))
+    }
+}
+
+impl<K, V, S, R> Debug for HashMap<K, V, S, R>
+where
+    K: 'static + Hash + Eq + Debug,
+    V: 'static + Debug,
+    S: 'static + BuildHasher,
+    R: Protect + ProtectPtr + Retire + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // The entries of a frozen bucket may be spread over two tables, so any resize that is
+        // under way is finished first.
+        let heads = loop {
+            let table = self.table.load::<Snapshot<_, R>>(SeqCst).unwrap();
+            let heads: Option<Vec<_>> = table
+                .buckets
+                .iter()
+                .map(|bucket| {
+                    let (head, tag) = bucket.load_tagged::<Snapshot<_, R>>(SeqCst);
+                    (tag & FROZEN == 0).then_some(head)
+                })
+                .collect();
+            match heads {
+                Some(heads) => break heads,
+                None => self.finish_resize(&table),
+            }
+        };
+        let mut map = f.debug_map();
+        for head in heads {
+            let mut curr = head.as_deref();
+            while let Some(node) = curr {
+                map.entry(&node.entry.key, &node.entry.value);
+                curr = node.next.as_deref();
+            }
+        }
+        map.finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: 'static, V: 'static> Default for HashMap<K, V, RandomState, StandardReclaimer> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> HashMapEntry<K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for HashMapEntry<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&(&self.key, &self.value), f)
+    }
+}
+
+impl<K, V> Deref for HashMapEntry<K, V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::HashMap;
+    use crate::smr::standard_reclaimer::StandardReclaimer;
+    use crate::Snapshot;
+    use std::hash::{BuildHasherDefault, Hasher};
+    use std::sync::atomic::Ordering::SeqCst;
+    use std::thread;
+
+    #[derive(Default)]
+    struct Colliding;
+
+    impl Hasher for Colliding {
+        fn finish(&self) -> u64 {
+            0
+        }
+        fn write(&mut self, _: &[u8]) {}
+    }
+
+    #[test]
+    fn test_hash_map_colliding_keys() {
+        // Every key shares one chain, however many buckets there are.
+        let map = HashMap::<_, _, _, StandardReclaimer>::with_hasher_in(
+            1,
+            BuildHasherDefault::<Colliding>::default(),
+        );
+        for i in 0..5 {
+            assert!(map.insert(i, i * 10).is_none());
+        }
+        let two = map.get(&2).unwrap();
+        assert_eq!(**map.insert(2, 21).unwrap(), 20);
+        assert_eq!(**map.remove(&0).unwrap(), 0);
+        assert!(map.remove(&0).is_none());
+
+        // The replaced entry remains readable.
+        assert_eq!((*two.key(), **two), (2, 20));
+        let mut entries: Vec<_> = (0..5).filter_map(|i| map.get(&i)).map(|e| **e).collect();
+        entries.sort();
+        assert_eq!(entries, [10, 21, 30, 40]);
+        assert_eq!(format!("{:?}", map.get(&4).unwrap()), "(4, 40)");
+    }
+
+    #[test]
+    fn test_hash_map_resize() {
+        let map = HashMap::with_buckets(1);
+        for i in 0..100 {
+            assert!(map.insert(i, i).is_none());
+        }
+        let table = map.table.load::<Snapshot<_>>(SeqCst).unwrap();
+        assert_eq!(table.buckets.len(), 128);
+        assert!((0..100).all(|i| map.get(&i).is_some_and(|e| **e == i)));
+        for i in (0..100).step_by(2) {
+            assert_eq!(map.remove(&i).map(|e| **e), Some(i));
+        }
+        assert_eq!((0..100).filter(|i| map.contains_key(i)).count(), 50);
+        assert_eq!(format!("{map:?}").matches(':').count(), 50);
+    }
+
+    #[test]
+    fn test_hash_map_concurrent_updates() {
+        const THREADS_COUNT: usize = 4;
+        const KEYS_PER_THREAD: usize = 25;
+
+        // Starting with a single bucket makes the map resize while it is being updated.
+        let map = HashMap::with_buckets(1);
+        // An entry that no thread updates must remain visible throughout.
+        map.insert(usize::MAX, 0);
+        thread::scope(|s| {
+            for t in 0..THREADS_COUNT {
+                let map = &map;
+                s.spawn(move || {
+                    let keys = t * KEYS_PER_THREAD..(t + 1) * KEYS_PER_THREAD;
+                    for k in keys.clone() {
+                        assert!(map.insert(k, k).is_none());
+                        assert!(map.contains_key(&usize::MAX));
+                    }
+                    for k in keys.clone().filter(|k| k % 2 == 0) {
+                        assert_eq!(map.remove(&k).map(|e| **e), Some(k));
+                    }
+                    for k in keys {
+                        assert_eq!(map.get(&k).map(|e| **e), (k % 2 == 1).then_some(k));
+                    }
+                });
+            }
+        });
+        assert_eq!(
+            (0..THREADS_COUNT * KEYS_PER_THREAD)
+                .filter(|k| map.contains_key(k))
+                .count(),
+            THREADS_COUNT * KEYS_PER_THREAD / 2
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use crate::smr::drc::{Protect, ProtectPtr, Retire};
+#[cfg(feature = "std")]
+use crate::smr::standard_reclaimer::StandardReclaimer;
+use crate::{Arc, AtomicArc, Snapshot, Strong};
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug, Formatter};
+use core::sync::atomic::Ordering::SeqCst;
+
+default_reclaimer! {
+    /// A lock-free, multi-producer, multi-consumer FIFO queue.
+    ///
+    /// This is the Michael-Scott queue: `head` always points to a dummy node whose successor holds
+    /// the front value, and `tail` points to the last node or lags behind it by one, in which case
+    /// any thread may advance it. Nodes are reference counted, so a node that has been dequeued
+    /// remains valid for as long as another thread holds a [`Snapshot`] of it.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::collections::Queue;
+    ///
+    /// let queue = Queue::new();
+    /// queue.enqueue(1);
+    /// queue.enqueue(2);
+    /// assert_eq!(queue.dequeue(), Some(1));
+    /// assert_eq!(queue.dequeue(), Some(2));
+    /// assert!(queue.is_empty());
+    /// ```
+    pub struct Queue<T: 'static, R: Protect + ProtectPtr + Retire + 'static> {
+        head: AtomicArc<QueueNode<T, R>, R>,
+        tail: AtomicArc<QueueNode<T, R>, R>,
+    }
+}
+
+struct QueueNode<T: 'static, R: Protect + Retire + 'static> {
+    // Written once before the node is published, and taken only by the thread whose CAS makes the
+    // node the new dummy.
+    value: UnsafeCell<Option<T>>,
+    next: AtomicArc<Self, R>,
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static> Queue<T, StandardReclaimer> {
+    pub fn new() -> Self {
+        Self::new_in()
+    }
+}
+
+impl<T: 'static, R: Protect + ProtectPtr + Retire + 'static> Queue<T, R> {
+    pub fn new_in() -> Self {
+        let dummy = Arc::<_, R>::new_in(QueueNode {
+            value: UnsafeCell::new(None),
+            next: AtomicArc::null(),
+        });
+        Self {
+            head: AtomicArc::from(&dummy),
+            tail: AtomicArc::from(&dummy),
+        }
+    }
+
+    /// Appends `value` to the back of the queue.
+    pub fn enqueue(&self, value: T) {
+        let node = Arc::<_, R>::new_in(QueueNode {
+            value: UnsafeCell::new(Some(value)),
+            next: AtomicArc::null(),
+        });
+        loop {
+            let tail = self.load_tail();
+            match tail.next.load::<Snapshot<_, R>>(SeqCst) {
+                None => {
+                    if tail
+                        .next
+                        .compare_exchange::<Snapshot<_, R>, _, Snapshot<_, R>>(
+                            None,
+                            Some(&node),
+                            SeqCst,
+                            SeqCst,
+                        )
+                        .is_ok()
+                    {
+                        // If this fails, another thread has already advanced the tail.
+                        self.advance_tail(&tail, &node);
+                        return;
+                    }
+                }
+                // The tail is lagging behind; help to advance it before retrying.
+                Some(next) => self.advance_tail(&tail, &next),
+            }
+        }
+    }
+
+    /// Removes and returns the value at the front of the queue, or [`None`] if it is empty.
+    pub fn dequeue(&self) -> Option<T> {
+        loop {
+            let head = self.head.load::<Snapshot<_, R>>(SeqCst).unwrap();
+            let next = head.next.load::<Snapshot<_, R>>(SeqCst)?;
+            let tail = self.load_tail();
+            if Snapshot::ptr_eq(&head, &tail) {
+                // Don't let the head overtake a lagging tail.
+                self.advance_tail(&tail, &next);
+            }
+            if self
+                .head
+                .compare_exchange::<_, _, Snapshot<_, R>>(Some(&head), Some(&next), SeqCst, SeqCst)
+                .is_ok()
+            {
+                // `next` is now the dummy node. The head never returns to a node, so this thread
+                // is the only one that will ever take its value.
+                return unsafe { (*next.value.get()).take() };
+            }
+        }
+    }
+
+    /// Returns `true` if the queue contains no values. As with any lock-free read, the result may
+    /// be stale as soon as it is returned.
+    pub fn is_empty(&self) -> bool {
+        let head = self.head.load::<Snapshot<_, R>>(SeqCst).unwrap();
+        head.next.is_none(SeqCst)
+    }
+
+    fn load_tail(&self) -> Snapshot<QueueNode<T, R>, R> {
+        self.tail.load(SeqCst).unwrap()
+    }
+
+    fn advance_tail<N: Strong<QueueNode<T, R>>>(
+        &self,
+        tail: &Snapshot<QueueNode<T, R>, R>,
+        next: &N,
+    ) {
+        let _ = self.tail.compare_exchange::<_, _, Snapshot<_, R>>(
+            Some(tail),
+            Some(next),
+            SeqCst,
+            SeqCst,
+        );
+    }
+}
+
+impl<T: 'static, R: Protect + ProtectPtr + Retire + 'static> Debug for Queue<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Queue")
+            .field("is_empty", &self.is_empty())
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static> Default for Queue<T, StandardReclaimer> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<T: 'static + Send, R: Protect + ProtectPtr + Retire + 'static> Send for Queue<T, R> {}
+
+unsafe impl<T: 'static + Send, R: Protect + ProtectPtr + Retire + 'static> Sync for Queue<T, R> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::Queue;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::SeqCst;
+    use std::thread;
+
+    #[test]
+    fn test_queue_concurrent_enqueue_and_dequeue() {
+        const THREADS_COUNT: usize = 4;
+        const ITERS_PER_THREAD: usize = 100;
+
+        let queue = Queue::new();
+        let val_counts: Vec<AtomicUsize> = (0..ITERS_PER_THREAD)
+            .map(|_| AtomicUsize::default())
+            .collect();
+        thread::scope(|s| {
+            for _ in 0..THREADS_COUNT {
+                s.spawn(|| {
+                    for i in 0..ITERS_PER_THREAD {
+                        queue.enqueue(i);
+                    }
+                });
+                s.spawn(|| {
+                    let mut dequeued = 0;
+                    while dequeued < ITERS_PER_THREAD {
+                        let Some(val) = queue.dequeue() else {
+                            continue;
+                        };
+                        val_counts[val].fetch_add(1, SeqCst);
+                        dequeued += 1;
+                    }
+                });
+            }
+        });
+
+        // Verify that every value was dequeued exactly once per producer.
+        assert!(queue.is_empty());
+        for count in val_counts.iter() {
+            assert_eq!(count.load(SeqCst), THREADS_COUNT);
+        }
+    }
+
+    #[test]
+    fn test_queue_order() {
+        let queue = Queue::default();
+        assert_eq!(queue.dequeue(), None);
+        for i in 0..5 {
+            queue.enqueue(i);
+        }
+        assert_eq!(queue.dequeue(), Some(0));
+        queue.enqueue(5);
+        assert_eq!(
+            (1..6).map(|_| queue.dequeue()).collect::<Vec<_>>(),
+            (1..6).map(Some).collect::<Vec<_>>()
+        );
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::smr::drc::{Protect, ProtectPtr, Retire};
+#[cfg(feature = "std")]
+use crate::smr::standard_reclaimer::StandardReclaimer;
+use crate::{Arc, AtomicArc, Snapshot};
+use core::fmt::{self, Debug, Formatter};
+use core::ops::Deref;
+use core::sync::atomic::Ordering::SeqCst;
+
+default_reclaimer! {
+    /// A lock-free, multi-producer, multi-consumer LIFO stack (a Treiber stack).
+    ///
+    /// Popped values are returned as [`Snapshot`]s of their nodes, which dereference to the value.
+    /// A node is immutable once it has been pushed, so it can be read by any number of threads
+    /// even after it has been popped.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::collections::Stack;
+    ///
+    /// let stack = Stack::new();
+    /// stack.push(1);
+    /// stack.push(2);
+    /// assert_eq!(stack.iter().map(|node| **node).collect::<Vec<_>>(), [2, 1]);
+    ///
+    /// assert_eq!(**stack.pop().unwrap(), 2);
+    /// assert_eq!(**stack.pop().unwrap(), 1);
+    /// assert!(stack.is_empty());
+    /// ```
+    pub struct Stack<T: 'static, R: Protect + ProtectPtr + Retire + 'static> {
+        top: AtomicArc<StackNode<T, R>, R>,
+    }
+}
+
+default_reclaimer! {
+    /// A node of a [`Stack`], which dereferences to its value.
+    pub struct StackNode<T: 'static, R: Retire + 'static> {
+        value: T,
+        next: Option<Arc<Self, R>>,
+    }
+}
+
+default_reclaimer! {
+    /// An iterator over the nodes of a [`Stack`], from top to bottom. See [`Stack::iter`].
+    pub struct Iter<T: 'static, R: ProtectPtr + Retire + 'static> {
+        next: Option<Snapshot<StackNode<T, R>, R>>,
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static> Stack<T, StandardReclaimer> {
+    pub fn new() -> Self {
+        Self::new_in()
+    }
+}
+
+impl<T: 'static, R: Protect + ProtectPtr + Retire + 'static> Stack<T, R> {
+    pub fn new_in() -> Self {
+        Self {
+            top: AtomicArc::null(),
+        }
+    }
+
+    /// Pushes `value` onto the top of the stack.
+    pub fn push(&self, value: T) {
+        let mut node = Arc::<_, R>::new_in(StackNode { value, next: None });
+        let mut top = self.top.load::<Arc<_, R>>(SeqCst);
+        loop {
+            // Safety: the node has not been published yet, so no other thread can access it.
+            unsafe {
+                Arc::get_mut_unchecked(&mut node).next = top;
+            }
+            match self.top.compare_exchange::<_, _, Arc<_, R>>(
+                node.next.as_ref(),
+                Some(&node),
+                SeqCst,
+                SeqCst,
+            ) {
+                Ok(()) => return,
+                Err(actual) => top = actual,
+            }
+        }
+    }
+
+    /// Removes the top node and returns it, or [`None`] if the stack is empty.
+    pub fn pop(&self) -> Option<Snapshot<StackNode<T, R>, R>> {
+        self.top
+            .fetch_update(SeqCst, SeqCst, |top| top.map(|node| node.next.clone()))
+            .ok()
+            .flatten()
+    }
+
+    /// Returns `true` if the stack contains no values. As with any lock-free read, the result may
+    /// be stale as soon as it is returned.
+    pub fn is_empty(&self) -> bool {
+        self.top.is_none(SeqCst)
+    }
+
+    /// Returns an iterator over the nodes that were in the stack when it was called, from top to
+    /// bottom. Concurrent pushes and pops do not affect the iterator, and it holds at most one
+    /// [`Snapshot`] at a time (in addition to those it has returned).
+    pub fn iter(&self) -> Iter<T, R> {
+        Iter {
+            next: self.top.load(SeqCst),
+        }
+    }
+}
+
+impl<T: 'static + Debug, R: Protect + ProtectPtr + Retire + 'static> Debug for Stack<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static> Default for Stack<T, StandardReclaimer> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static + Debug, R: Retire + 'static> Debug for StackNode<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.value, f)
+    }
+}
+
+impl<T: 'static, R: Retire + 'static> Deref for StackNode<T, R> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: 'static, R: ProtectPtr + Retire + 'static> Iterator for Iter<T, R> {
+    type Item = Snapshot<StackNode<T, R>, R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr = self.next.take()?;
+        self.next = curr.next.as_ref().map(Snapshot::from);
+        Some(curr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::Stack;
+
+    #[test]
+    fn test_stack_iter_is_unaffected_by_pops() {
+        let stack = Stack::new();
+        for i in 0..4 {
+            stack.push(i);
+        }
+        assert_eq!(format!("{:?}", stack), "[3, 2, 1, 0]");
+        let mut iter = stack.iter();
+        assert_eq!(iter.next().map(|node| **node), Some(3));
+
+        while stack.pop().is_some() {}
+        assert!(stack.is_empty());
+        assert_eq!(iter.map(|node| **node).collect::<Vec<_>>(), [2, 1, 0]);
+        assert_eq!(format!("{:?}", stack), "[]");
+    }
+}
@@ -1,18 +1,122 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 /// Crate-level documentation.
 #[doc = include_str!("../README.md")]
 pub mod docs {}
 
+pub use any_arc::AnyArc;
 pub use atomics::AtomicArc;
+pub use atomics::AtomicArcArray;
+pub use atomics::AtomicArcInit;
+pub use atomics::AtomicArcTagged;
 pub use atomics::AtomicWeak;
 pub use atomics::Shared;
 pub use atomics::Strong;
+pub use atomics::TransferError;
+pub use by_address::ByAddress;
+pub use shared_ptrs::AllocError;
 pub use shared_ptrs::Arc;
+pub use shared_ptrs::ArcDyn;
 pub use shared_ptrs::AsPtr;
+pub use shared_ptrs::Reclaimed;
 pub use shared_ptrs::Snapshot;
 pub use shared_ptrs::Weak;
+pub use slice::ArcSlice;
+#[cfg(feature = "std")]
+pub use smr::drc::{pin, Pin};
+pub use snapshot_iter::SnapshotIter;
+
+/// Declares a struct whose reclaimer parameter `R` defaults to [`StandardReclaimer`] when the
+/// `std` feature is enabled. Without `std` there is no default reclaimer, so `R` must be named.
+///
+/// [`StandardReclaimer`]: `smr::standard_reclaimer::StandardReclaimer`
+macro_rules! default_reclaimer {
+    // A struct with a third type parameter that has its own default (e.g. a hasher), which also
+    // only applies with `std`.
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<
+            $t:ident: 'static,
+            $u:ident: 'static,
+            $s:ident: 'static = $sdef:ty,
+            R: $bound:ident $(+ $bounds:tt)*
+        > $($body:tt)*
+    ) => {
+        #[cfg(feature = "std")]
+        $(#[$attr])*
+        $vis struct $name<
+            $t: 'static,
+            $u: 'static,
+            $s: 'static = $sdef,
+            R: $bound $(+ $bounds)* = $crate::smr::standard_reclaimer::StandardReclaimer,
+        > $($body)*
+
+        #[cfg(not(feature = "std"))]
+        $(#[$attr])*
+        $vis struct $name<$t: 'static, $u: 'static, $s: 'static, R: $bound $(+ $bounds)*> $($body)*
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<
+            T: 'static,
+            $(const $c:ident: $cty:ty,)?
+            R: $bound:ident $(+ $bounds:tt)*
+        > $($body:tt)*
+    ) => {
+        #[cfg(feature = "std")]
+        $(#[$attr])*
+        $vis struct $name<
+            T: 'static,
+            $(const $c: $cty,)?
+            R: $bound $(+ $bounds)* = $crate::smr::standard_reclaimer::StandardReclaimer,
+        > $($body)*
+
+        #[cfg(not(feature = "std"))]
+        $(#[$attr])*
+        $vis struct $name<T: 'static, $(const $c: $cty,)? R: $bound $(+ $bounds)*> $($body)*
+    };
+    // A struct whose only parameter is the reclaimer (e.g. one that erases `T`).
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<R: $bound:ident $(+ $bounds:tt)*> $($body:tt)*
+    ) => {
+        #[cfg(feature = "std")]
+        $(#[$attr])*
+        $vis struct $name<
+            R: $bound $(+ $bounds)* = $crate::smr::standard_reclaimer::StandardReclaimer,
+        > $($body)*
+
+        #[cfg(not(feature = "std"))]
+        $(#[$attr])*
+        $vis struct $name<R: $bound $(+ $bounds)*> $($body)*
+    };
+}
 
+/// Items used by this crate's exported macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+}
+
+pub(crate) mod any_arc;
 pub(crate) mod atomics;
+pub(crate) mod by_address;
 pub(crate) mod shared_ptrs;
+pub(crate) mod slice;
+pub(crate) mod snapshot_iter;
+
+/// Lock-free data structures built on [`AtomicArc`].
+pub mod collections {
+    pub use hash_map::{HashMap, HashMapEntry};
+    pub use queue::Queue;
+    pub use stack::{Iter as StackIter, Stack, StackNode};
+
+    pub(crate) mod hash_map;
+    pub(crate) mod queue;
+    pub(crate) mod stack;
+}
 
 /// Traits and structs pertaining to safe memory reclamation.
 pub mod smr {
@@ -20,11 +124,26 @@ pub mod smr {
     pub mod drc;
 
     /// The crate-default reclaimer.
+    #[cfg(feature = "std")]
     pub mod standard_reclaimer;
+
+    /// A reclaimer for interoperating with `crossbeam-epoch`.
+    #[cfg(feature = "crossbeam-epoch")]
+    pub mod epoch_reclaimer;
+
+    /// A reclaimer that reclaims objects as early as possible, for testing.
+    #[cfg(feature = "deterministic-reclaimer")]
+    pub mod deterministic_reclaimer;
 }
 
 pub(crate) mod utils {
+    #[cfg(feature = "std")]
     pub(crate) mod helpers;
+    pub(crate) mod sync;
+    #[cfg(test)]
+    pub(crate) mod test_utils;
+    #[cfg(feature = "std")]
     pub(crate) mod unrolled_linked_list;
+    #[cfg(feature = "std")]
     pub(crate) mod unsafe_arc;
 }
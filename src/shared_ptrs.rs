@@ -1,61 +1,141 @@
-use crate::smr::drc::{ProtectPtr, Release, Retire};
+use crate::smr::drc::{ProtectPtr, Release, Retire, Retirement};
+#[cfg(feature = "std")]
 use crate::smr::standard_reclaimer::StandardReclaimer;
-use crate::utils::helpers::alloc_box_ptr;
-use std::alloc::{dealloc, Layout};
-use std::marker::PhantomData;
-use std::ops::Deref;
-use std::ptr::NonNull;
-use std::sync::atomic::Ordering::{Acquire, Relaxed, SeqCst};
-use std::sync::atomic::{fence, AtomicUsize};
-use std::{mem, ptr};
-
-/// A reimplementation of [`std::sync::Arc`].
-///
-/// This module's `Arc` is essentially identical to the standard library's, with just
-/// two constraints:
-///
-/// - `T` has a `'static` lifetime bound, as the `Arc` might not be destroyed immediately when the
-/// reference count reaches zero.
-/// - `T` must be [`Sized`] for compatability with [`AtomicArc`], which wraps [`AtomicPtr`],
-/// which also has this bound.
-///
-/// See [`std::sync::Arc`] for per-method documentation.
-///
-/// # Examples:
-/// ```
-/// use aarc::Arc;
-///
-/// let x = Arc::new(53);
-/// assert_eq!(*x, 53);
-///
-/// let y = Arc::new(53);
-/// assert_eq!(*x, *y);
-///
-/// assert!(!Arc::ptr_eq(&x, &y));
-///
-/// let w = Arc::downgrade(&x);
-/// assert_eq!(Arc::weak_count(&x), 1);
-/// ```
-///
-/// [`AtomicArc`]: `super::AtomicArc`
-/// [`AtomicPtr`]: `std::sync::atomic::AtomicPtr`
-pub struct Arc<T: 'static, R: Retire = StandardReclaimer> {
-    ptr: NonNull<ArcInner<T>>,
-    phantom: PhantomData<ArcInner<T>>,
-    phantom_r: PhantomData<R>,
+use crate::utils::sync::{fence, AtomicUsize};
+use alloc::alloc::{alloc, alloc_zeroed, dealloc, handle_alloc_error, Layout};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use core::sync::atomic::Ordering::{self, Acquire, Relaxed, SeqCst};
+use core::{mem, ptr};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The number of `Arc` allocations that have not yet been freed, across all reclaimers. This is
+/// only tracked in debug builds.
+#[cfg(debug_assertions)]
+pub(crate) static LIVE_ALLOCATIONS: core::sync::atomic::AtomicUsize =
+    core::sync::atomic::AtomicUsize::new(0);
+
+default_reclaimer! {
+    /// A reimplementation of [`std::sync::Arc`].
+    ///
+    /// This module's `Arc` is essentially identical to the standard library's, with just
+    /// two constraints:
+    ///
+    /// - `T` has a `'static` lifetime bound, as the `Arc` might not be destroyed immediately when the
+    ///   reference count reaches zero.
+    /// - `T` must be [`Sized`] for compatability with [`AtomicArc`], which wraps [`AtomicPtr`],
+    ///   which also has this bound.
+    ///
+    /// See [`std::sync::Arc`] for per-method documentation.
+    ///
+    /// Because `T` is destroyed by the reclaimer rather than by the last `Arc`, a panic in `T`'s
+    /// [`Drop`] implementation does not propagate to the caller. The panic is caught (the panic hook
+    /// still reports it), the allocation is freed regardless, and the remaining retired objects are
+    /// reclaimed as usual.
+    ///
+    /// As an optimization, `Arc`s of a zero-sized `T` that doesn't implement [`Drop`] (e.g. `()`)
+    /// all point to the same static allocation, so creating one never allocates. The reference
+    /// counts of that allocation are shared and saturated, so `get_mut` and `try_unwrap` always
+    /// fail for such an `Arc`.
+    ///
+    /// # Trait objects
+    /// Since `Arc<dyn Trait>` is not possible, use an [`ArcDyn`], which boxes the trait object. It
+    /// can be held by an [`AtomicArc`] and derefs to the trait object, at the cost of a second
+    /// allocation and indirection per value. Create one with [`arc_dyn!`].
+    /// ```
+    /// use std::fmt::Display;
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{arc_dyn, Arc, ArcDyn, AtomicArc, Snapshot};
+    ///
+    /// let atomic: AtomicArc<Box<dyn Display + Send + Sync>> = AtomicArc::from(&arc_dyn!(53));
+    /// let snapshot = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+    /// assert_eq!(snapshot.to_string(), "53");
+    ///
+    /// let new: ArcDyn<dyn Display + Send + Sync> = arc_dyn!("fifty-three");
+    /// atomic.store(Some(&new), SeqCst);
+    /// assert_eq!(atomic.load::<Arc<_>>(SeqCst).unwrap().to_string(), "fifty-three");
+    /// ```
+    ///
+    /// [`arc_dyn!`]: `crate::arc_dyn`
+    /// # Examples:
+    /// ```
+    /// use aarc::Arc;
+    ///
+    /// let x = Arc::new(53);
+    /// assert_eq!(*x, 53);
+    ///
+    /// let y = Arc::new(53);
+    /// assert_eq!(*x, *y);
+    ///
+    /// assert!(!Arc::ptr_eq(&x, &y));
+    ///
+    /// let w = Arc::downgrade(&x);
+    /// assert_eq!(Arc::weak_count(&x), 1);
+    /// ```
+    ///
+    /// [`ArcSlice`]: `crate::ArcSlice`
+    /// [`AtomicArc`]: `super::AtomicArc`
+    /// [`AtomicPtr`]: `std::sync::atomic::AtomicPtr`
+    // Transparent so that an `AtomicArc` can lend out its pointer as an `Arc`.
+    #[repr(transparent)]
+    pub struct Arc<T: 'static, R: Retire> {
+        ptr: NonNull<ArcInner<T>>,
+        phantom: PhantomData<ArcInner<T>>,
+        phantom_r: PhantomData<R>,
+    }
 }
 
+#[cfg(feature = "std")]
 impl<T: 'static> Arc<T, StandardReclaimer> {
     pub fn new(data: T) -> Self {
         Arc::<_, StandardReclaimer>::new_in(data)
     }
+    /// Creates an `Arc` whose value is passed to `on_reclaim` instead of being dropped. See
+    /// [`Arc::new_with_reclaim_in`].
+    pub fn new_with_reclaim(data: T, on_reclaim: fn(*mut T)) -> Arc<Reclaimed<T>> {
+        Arc::<_, StandardReclaimer>::new_with_reclaim_in(data, on_reclaim)
+    }
+    /// Creates an `Arc`, or returns an error if the allocation fails. See [`Arc::try_new_in`].
+    pub fn try_new(data: T) -> Result<Self, AllocError> {
+        Arc::<_, StandardReclaimer>::try_new_in(data)
+    }
+    /// Creates an `Arc` with uninitialized contents. See [`Arc::new_uninit_in`].
+    pub fn new_uninit() -> Arc<MaybeUninit<T>, StandardReclaimer> {
+        Arc::<_, StandardReclaimer>::new_uninit_in()
+    }
+    /// Creates an `Arc` with uninitialized contents, or returns an error if the allocation fails.
+    /// See [`Arc::try_new_uninit_in`].
+    pub fn try_new_uninit() -> Result<Arc<MaybeUninit<T>, StandardReclaimer>, AllocError> {
+        Arc::<_, StandardReclaimer>::try_new_uninit_in()
+    }
+    /// Creates an `Arc` whose contents are filled with zero bytes. See [`Arc::new_zeroed_in`].
+    pub fn new_zeroed() -> Arc<MaybeUninit<T>, StandardReclaimer> {
+        Arc::<_, StandardReclaimer>::new_zeroed_in()
+    }
 }
 
 impl<T: 'static, R: Retire> Arc<T, R> {
     pub fn downgrade(this: &Arc<T, R>) -> Weak<T, R> {
         unsafe { Weak::clone_from_raw(this.ptr.as_ptr().cast()) }
     }
-    #[allow(clippy::missing_safety_doc)]
+    /// Reconstructs an `Arc` from a pointer returned by [`Arc::into_raw`], taking over the strong
+    /// reference that it carried.
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by `into_raw` on an `Arc<T, R>` with the same `T` and `R`, and
+    /// each such pointer may only be converted back once. The layout of the allocation around the
+    /// value is unspecified and may change between versions of this crate, so raw pointers must
+    /// not be persisted or exchanged with code built against another version, and offsets from
+    /// them must not be relied upon.
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         Self {
             ptr: NonNull::new_unchecked(ptr as *mut ArcInner<T>),
@@ -67,32 +147,273 @@ impl<T: 'static, R: Retire> Arc<T, R> {
     pub unsafe fn increment_strong_count(ptr: *const T) {
         (*(ptr as *const ArcInner<T>)).increment_strong_count();
     }
+    /// Returns a raw pointer to the value, without affecting the reference counts. The pointer is
+    /// valid for as long as there are strong references to the value.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::Arc;
+    ///
+    /// let x = Arc::new(53);
+    /// let y = x.clone();
+    /// assert_eq!(Arc::as_ptr(&x), Arc::as_ptr(&y));
+    /// assert_eq!(unsafe { *Arc::as_ptr(&x) }, 53);
+    /// ```
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.ptr.as_ptr() as *const T
+    }
+    /// Consumes the `Arc` and returns a pointer to the value, which carries its strong reference.
+    /// Use [`Arc::from_raw`] to convert it back and release the reference.
     pub fn into_raw(this: Self) -> *const T {
         let ptr = Self::as_ptr(&this);
         mem::forget(this);
         ptr
     }
     pub fn new_in(data: T) -> Self {
-        unsafe {
-            Self {
-                ptr: NonNull::new_unchecked(alloc_box_ptr(ArcInner {
-                    data,
-                    strong: AtomicUsize::new(1),
-                    weak: AtomicUsize::new(1),
-                })),
-                phantom: PhantomData,
-                phantom_r: PhantomData,
+        Self::try_new_in(data).unwrap_or_else(|_| Self::alloc_failed())
+    }
+    /// Similar to [`Arc::new_in`], but returns an error instead of aborting if the allocation
+    /// fails. `data` is dropped in that case.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::Arc;
+    ///
+    /// let x = Arc::try_new(53).expect("out of memory");
+    /// assert_eq!(*x, 53);
+    /// ```
+    pub fn try_new_in(data: T) -> Result<Self, AllocError> {
+        #[cfg(not(loom))]
+        if Self::SHARES_ZST_INNER {
+            // The value has no destructor, so forgetting it is equivalent to dropping it.
+            mem::forget(data);
+            let inner = ptr::addr_of!(ZST_INNER) as *mut ArcInner<T>;
+            unsafe {
+                (*inner).increment_strong_count();
+                return Ok(Self::from_raw(inner as *const T));
             }
         }
+        Self::try_alloc_in(data)
+    }
+    /// Whether `Arc`s of `T` share [`ZST_INNER`] instead of allocating. A zero-sized value
+    /// without a destructor is indistinguishable from any other, so only the alignment matters.
+    const SHARES_ZST_INNER: bool = mem::size_of::<T>() == 0
+        && mem::align_of::<T>() <= mem::align_of::<ArcInner<()>>()
+        && !mem::needs_drop::<T>();
+    /// Creates an `Arc` whose value is passed to `on_reclaim` instead of being dropped when it is
+    /// reclaimed, e.g. to return a buffer to a pool rather than free it. The value is wrapped in a
+    /// [`Reclaimed`], which dereferences to it.
+    ///
+    /// `on_reclaim` takes ownership of the value: it must drop it or move it out (e.g. with
+    /// [`ptr::read`]). The allocation itself is freed as usual afterwards, so the pointer must not
+    /// be used once `on_reclaim` returns. To move the value out of the `Arc` without calling
+    /// `on_reclaim`, use [`Arc::try_unwrap`] followed by [`Reclaimed::into_inner`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ptr;
+    /// use std::sync::Mutex;
+    /// use aarc::smr::standard_reclaimer::StandardReclaimer;
+    /// use aarc::Arc;
+    ///
+    /// static POOL: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+    ///
+    /// let buf = Arc::new_with_reclaim(vec![0; 4096], |buf| {
+    ///     POOL.lock().unwrap().push(unsafe { ptr::read(buf) });
+    /// });
+    /// drop(buf);
+    /// StandardReclaimer::flush();
+    /// assert_eq!(POOL.lock().unwrap().pop().unwrap().len(), 4096);
+    /// ```
+    pub fn new_with_reclaim_in(data: T, on_reclaim: fn(*mut T)) -> Arc<Reclaimed<T>, R> {
+        Arc::new_in(Reclaimed::new(data, on_reclaim))
+    }
+    fn try_alloc_in(data: T) -> Result<Self, AllocError> {
+        unsafe {
+            let inner = Self::try_alloc_inner(false)?;
+            ptr::addr_of_mut!((*inner).data).write(data);
+            Ok(Self::from_raw(inner as *const T))
+        }
+    }
+    /// Allocates an `ArcInner` and initializes its counts, but not its data. The data is left
+    /// uninitialized, or filled with zero bytes if `zeroed` is set.
+    unsafe fn try_alloc_inner(zeroed: bool) -> Result<*mut ArcInner<T>, AllocError> {
+        // `ArcInner` contains the counts, so the layout is never zero-sized.
+        Self::try_alloc_layout(Layout::new::<ArcInner<T>>(), zeroed, 1)
+    }
+    /// Allocates an `ArcInner` followed by trailing data (e.g. the elements of an [`ArcSlice`]),
+    /// where `layout` covers both. The counts are initialized, but the data is not: it must start
+    /// with `layout`, which is used to free the allocation (see [`TRAILING`]).
+    ///
+    /// [`ArcSlice`]: `crate::ArcSlice`
+    pub(crate) unsafe fn try_alloc_trailing(
+        layout: Layout,
+    ) -> Result<*mut ArcInner<T>, AllocError> {
+        Self::try_alloc_layout(layout, false, 1 | TRAILING)
+    }
+    unsafe fn try_alloc_layout(
+        layout: Layout,
+        zeroed: bool,
+        weak: usize,
+    ) -> Result<*mut ArcInner<T>, AllocError> {
+        let inner = if zeroed {
+            alloc_zeroed(layout)
+        } else {
+            alloc(layout)
+        }
+        .cast::<ArcInner<T>>();
+        if inner.is_null() {
+            return Err(AllocError);
+        }
+        ptr::addr_of_mut!((*inner).strong).write(AtomicUsize::new(1));
+        ptr::addr_of_mut!((*inner).weak).write(AtomicUsize::new(weak));
+        #[cfg(debug_assertions)]
+        LIVE_ALLOCATIONS.fetch_add(1, Relaxed);
+        Ok(inner)
+    }
+    fn alloc_failed() -> ! {
+        handle_alloc_error(Layout::new::<ArcInner<T>>())
+    }
+    /// Creates an `Arc` with uninitialized contents. The reference counts are initialized as
+    /// usual.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::Arc;
+    ///
+    /// let mut x = Arc::<u32>::new_uninit();
+    /// let x = unsafe {
+    ///     Arc::get_mut_unchecked(&mut x).write(53);
+    ///     x.assume_init()
+    /// };
+    /// assert_eq!(*x, 53);
+    /// ```
+    pub fn new_uninit_in() -> Arc<MaybeUninit<T>, R> {
+        Self::try_new_uninit_in().unwrap_or_else(|_| Self::alloc_failed())
+    }
+    /// Similar to [`Arc::new_uninit_in`], but returns an error instead of aborting if the
+    /// allocation fails.
+    pub fn try_new_uninit_in() -> Result<Arc<MaybeUninit<T>, R>, AllocError> {
+        // `MaybeUninit` never needs to be dropped, but the `T` that it will contain might.
+        // The contents are not moved into place, so they can be larger than the stack.
+        if Self::SHARES_ZST_INNER {
+            Arc::try_new_in(MaybeUninit::uninit())
+        } else {
+            unsafe { Self::try_alloc_inner(false).map(|inner| Arc::from_raw(inner.cast())) }
+        }
+    }
+    /// Creates an `Arc` whose contents are filled with zero bytes. The reference counts are
+    /// initialized as usual.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::Arc;
+    ///
+    /// let x = Arc::<u32>::new_zeroed();
+    /// assert_eq!(unsafe { *x.assume_init() }, 0);
+    /// ```
+    pub fn new_zeroed_in() -> Arc<MaybeUninit<T>, R> {
+        let arc = if Self::SHARES_ZST_INNER {
+            Arc::try_new_in(MaybeUninit::zeroed())
+        } else {
+            unsafe { Self::try_alloc_inner(true).map(|inner| Arc::from_raw(inner.cast())) }
+        };
+        arc.unwrap_or_else(|_| Self::alloc_failed())
+    }
+    /// Returns a mutable reference to the value without any check.
+    ///
+    /// # Safety
+    /// No other `Arc`, [`Weak`], [`Snapshot`], or atomic may be used to access the value for the
+    /// duration of the returned borrow. This holds, for example, for an `Arc` that has just been
+    /// created and not yet shared.
+    pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
+        &mut (*this.ptr.as_ptr()).data
+    }
+    /// Returns `true` if there are no other `Arc` or [`Weak`] pointers to the same allocation.
+    ///
+    /// [`Snapshot`]s do not contribute to reference counts, so a unique `Arc` may still be read
+    /// through `Snapshot`s held by other threads. A `true` result is therefore not, by itself,
+    /// permission to mutate the value.
+    pub fn is_unique(this: &Self) -> bool {
+        let inner = unsafe { &*this.ptr.as_ptr() };
+        inner.weak_count(Acquire) == 1 && inner.strong.load(Acquire) == 1
+    }
+    /// Returns `true` if there are other `Arc` or [`Weak`] pointers to the same allocation.
+    ///
+    /// This is a cheaper, relaxed counterpart to [`Arc::is_unique`], suitable for heuristics (e.g.
+    /// deciding whether copying is likely to be necessary). The result may be stale by the time it
+    /// is returned.
+    pub fn is_shared(this: &Self) -> bool {
+        let inner = unsafe { &*this.ptr.as_ptr() };
+        inner.strong.load(Relaxed) != 1 || inner.weak_count(Relaxed) != 1
     }
     pub fn ptr_eq(this: &Self, other: &Self) -> bool {
         ptr::eq(Self::as_ptr(this), Self::as_ptr(other))
     }
+    /// Returns the number of `Arc`s (including those held by [`AtomicArc`]s) pointing to this
+    /// allocation. [`Snapshot`]s are not counted.
+    ///
+    /// [`AtomicArc`]: `crate::AtomicArc`
     pub fn strong_count(this: &Self) -> usize {
         unsafe { (*this.ptr.as_ptr()).strong.load(Relaxed) }
     }
+    /// Returns the number of [`Weak`]s (including those held by [`AtomicWeak`]s) pointing to this
+    /// allocation.
+    ///
+    /// [`AtomicWeak`]: `crate::AtomicWeak`
     pub fn weak_count(this: &Self) -> usize {
-        unsafe { (*this.ptr.as_ptr()).weak.load(Relaxed) - 1 }
+        unsafe { (*this.ptr.as_ptr()).weak_count(Relaxed) - 1 }
+    }
+    /// Drops a group of `Arc`s at once.
+    ///
+    /// This is equivalent to dropping each `Arc` individually, but any objects whose strong
+    /// counts reach zero are handed to the reclaimer together, allowing it to amortize the cost
+    /// of retiring them (e.g. when dropping a large `Vec<Arc<T>>`).
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::Arc;
+    ///
+    /// let arcs: Vec<_> = (0..100).map(Arc::new).collect();
+    /// Arc::drop_batch(arcs);
+    /// ```
+    pub fn drop_batch<I: IntoIterator<Item = Self>>(arcs: I) {
+        let retirements: Vec<_> = arcs
+            .into_iter()
+            .filter_map(|arc| {
+                let inner = arc.ptr.as_ptr();
+                mem::forget(arc);
+                unsafe { Self::decrement_strong_count(inner) }
+            })
+            .collect();
+        if !retirements.is_empty() {
+            R::retire_many(retirements);
+        }
+    }
+    /// Decrements the strong count and, if it reaches zero, returns the retirement that will
+    /// destroy the value.
+    unsafe fn decrement_strong_count(inner: *mut ArcInner<T>) -> Option<Retirement> {
+        if (*inner).strong.fetch_sub(1, SeqCst) != 1 {
+            return None;
+        }
+        fence(Acquire);
+        Some((
+            inner as *mut u8,
+            Box::new(move || {
+                if (*inner).strong.load(SeqCst) == 0 {
+                    // Take ownership of the implicit weak reference first so that the
+                    // allocation is still released if T's destructor panics.
+                    //
+                    // Even if it is the last weak reference, the allocation can't be freed
+                    // here. A `Snapshot` that was waited out before this function ran may have
+                    // created a `Weak` (e.g. an `AtomicWeak`), and another thread may have
+                    // loaded the pointer from it since, so dropping the implicit weak reference
+                    // retires the deallocation to wait for those threads as well.
+                    let _weak = Weak::<T, R>::from_raw(inner as *const T);
+                    ptr::drop_in_place(inner as *mut T);
+                }
+            }),
+        ))
     }
     pub(crate) unsafe fn try_increment_strong_count(ptr: *const T) -> bool {
         (*(ptr as *const ArcInner<T>))
@@ -102,12 +423,156 @@ impl<T: 'static, R: Retire> Arc<T, R> {
     }
 }
 
+impl<T: 'static, R: Retire> Arc<MaybeUninit<T>, R> {
+    /// Converts to `Arc<T, R>`.
+    ///
+    /// # Safety
+    /// The contents must have been fully initialized, as with [`MaybeUninit::assume_init`].
+    pub unsafe fn assume_init(self) -> Arc<T, R> {
+        Arc::from_raw(Arc::into_raw(self).cast())
+    }
+}
+
+impl<T: 'static, R: ProtectPtr + Retire> Arc<T, R> {
+    /// Returns the inner value if `this` is the only pointer to it and no thread can observe it;
+    /// otherwise, returns `this` unchanged.
+    ///
+    /// Unlike with [`std::sync::Arc::try_unwrap`], a strong count of one does not mean that the
+    /// value is uniquely owned, because [`Snapshot`]s do not contribute to the strong count and
+    /// other threads may be in the middle of loading the pointer. Here, "uniquely owned" means
+    /// that the strong count is one *and* the reclaimer reports that the pointer is not protected
+    /// (see [`ProtectPtr::is_protected`]). That check is conservative, so `try_unwrap` may fail
+    /// spuriously while other threads are reading unrelated [`AtomicArc`]s, and it always fails
+    /// with reclaimers that cannot track protections. Unlike with the standard library, [`Weak`]s
+    /// also cause it to fail, as with [`Arc::get_mut`].
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::{Arc, Snapshot};
+    ///
+    /// let x = Arc::new(53);
+    /// let snapshot = Snapshot::from(&x);
+    /// let Err(x) = Arc::try_unwrap(x) else {
+    ///     unreachable!("the snapshot still protects the value");
+    /// };
+    /// drop(snapshot);
+    /// assert_eq!(Arc::try_unwrap(x).ok(), Some(53));
+    /// ```
+    ///
+    /// [`AtomicArc`]: `super::AtomicArc`
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        // A strong count of zero is final, since other threads (e.g. upgrading a `Weak`) take it to
+        // mean that the value is gone, so uniqueness is checked before it is decremented.
+        if !Self::is_exclusive(&this) {
+            return Err(this);
+        }
+        unsafe {
+            let inner = this.ptr.as_ptr();
+            if (*inner)
+                .strong
+                .compare_exchange(1, 0, SeqCst, Relaxed)
+                .is_err()
+            {
+                return Err(this);
+            }
+            let data = ptr::read(&(*inner).data);
+            drop(Weak::<T, R>::from_raw(inner as *const T));
+            mem::forget(this);
+            Ok(data)
+        }
+    }
+    /// Returns a mutable reference to the value if `this` is uniquely owned, in the sense
+    /// described by [`Arc::try_unwrap`]: there are no other `Arc`s or [`Weak`]s, and the
+    /// reclaimer reports that no thread can observe the value.
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        Self::is_exclusive(this).then(|| unsafe { &mut (*this.ptr.as_ptr()).data })
+    }
+    /// Returns a mutable reference to the value, first cloning it into a new allocation if
+    /// `this` is not uniquely owned (see [`Arc::get_mut`]).
+    ///
+    /// Because [`Snapshot`]s do not contribute to the reference counts, uniqueness is judged
+    /// conservatively: any protection that might cover the value, or any thread in a critical
+    /// section, causes a clone. This may therefore clone more often than
+    /// [`std::sync::Arc::make_mut`], but never too rarely.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::Arc;
+    ///
+    /// let mut x = Arc::new(53);
+    /// let y = x.clone();
+    /// *Arc::make_mut(&mut x) += 1; // clones, as y shares the value
+    /// assert_eq!((*x, *y), (54, 53));
+    ///
+    /// *Arc::make_mut(&mut x) += 1; // x is now unique, so no clone is needed
+    /// assert_eq!(*x, 55);
+    /// ```
+    pub fn make_mut(this: &mut Self) -> &mut T
+    where
+        T: Clone,
+    {
+        if !Self::is_exclusive(this) {
+            *this = Self::new_in(T::clone(this));
+        }
+        unsafe { &mut (*this.ptr.as_ptr()).data }
+    }
+    fn is_exclusive(this: &Self) -> bool {
+        let inner = unsafe { &*this.ptr.as_ptr() };
+        // The strong count is checked again after the scan in case it was incremented by a thread
+        // that left its critical section in the meantime. The weak count is compared including
+        // `TRAILING`, so that a value followed by trailing data is never moved out or swapped.
+        inner.strong.load(SeqCst) == 1
+            && inner.weak.load(SeqCst) == 1
+            && !R::is_protected(this.ptr.as_ptr() as *mut u8)
+            && inner.strong.load(SeqCst) == 1
+    }
+}
+
 impl<T: 'static, R: Retire> Clone for Arc<T, R> {
     fn clone(&self) -> Self {
         unsafe { Self::clone_from_raw(self.ptr.as_ptr().cast()) }
     }
 }
 
+impl<T: 'static + Debug, R: Retire> Debug for Arc<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: 'static + Default, R: Retire> Default for Arc<T, R> {
+    /// Creates an `Arc` containing `T::default()`.
+    fn default() -> Self {
+        Self::new_in(T::default())
+    }
+}
+
+impl<T: 'static + PartialEq, R: Retire> PartialEq for Arc<T, R> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: 'static + Eq, R: Retire> Eq for Arc<T, R> {}
+
+impl<T: 'static + PartialOrd, R: Retire> PartialOrd for Arc<T, R> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: 'static + Ord, R: Retire> Ord for Arc<T, R> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: 'static + Hash, R: Retire> Hash for Arc<T, R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
 impl<T: 'static, R: Retire> Deref for Arc<T, R> {
     type Target = T;
 
@@ -116,21 +581,23 @@ impl<T: 'static, R: Retire> Deref for Arc<T, R> {
     }
 }
 
+impl<T: 'static, R: Retire> AsRef<T> for Arc<T, R> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: 'static, R: Retire> Borrow<T> for Arc<T, R> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
 impl<T: 'static, R: Retire> Drop for Arc<T, R> {
     fn drop(&mut self) {
         unsafe {
-            let inner = self.ptr.as_ptr();
-            if (*inner).strong.fetch_sub(1, SeqCst) == 1 {
-                fence(Acquire);
-                R::retire(
-                    inner as *mut u8,
-                    Box::new(move || {
-                        if (*inner).strong.load(SeqCst) == 0 {
-                            ptr::drop_in_place(inner as *mut T);
-                            drop(Weak::<T, R>::from_raw(inner as *const T));
-                        }
-                    }),
-                );
+            if let Some((ptr, f)) = Self::decrement_strong_count(self.ptr.as_ptr()) {
+                R::retire(ptr, f);
             }
         }
     }
@@ -140,18 +607,76 @@ unsafe impl<T: 'static + Send + Sync, R: Retire> Send for Arc<T, R> {}
 
 unsafe impl<T: 'static + Send + Sync, R: Retire> Sync for Arc<T, R> {}
 
-/// A reimplementation of [`std::sync::Weak`].
+/// An [`Arc`] holding a trait object, such as `ArcDyn<dyn Display + Send + Sync>`.
 ///
-/// See [`Arc`] for details on how this struct differs from the standard library's.
+/// `Arc<dyn Trait>` is not possible because `Arc` requires `T: Sized`, so the trait object is
+/// boxed instead. An `ArcDyn` is an ordinary `Arc`, so it can be held by an [`AtomicArc`] or a
+/// [`Snapshot`], and it derefs to the trait object. Use [`arc_dyn!`] to create one.
 ///
-/// See [`std::sync::Weak`] for per-method documentation.
-pub struct Weak<T: 'static, R: Retire = StandardReclaimer> {
-    ptr: NonNull<ArcInner<T>>,
-    phantom_r: PhantomData<R>,
+/// [`AtomicArc`]: `crate::AtomicArc`
+/// [`arc_dyn!`]: `crate::arc_dyn`
+#[cfg(feature = "std")]
+pub type ArcDyn<D, R = StandardReclaimer> = Arc<Box<D>, R>;
+
+/// An [`Arc`] holding a trait object, such as `ArcDyn<dyn Display + Send + Sync, R>`.
+///
+/// `Arc<dyn Trait>` is not possible because `Arc` requires `T: Sized`, so the trait object is
+/// boxed instead. An `ArcDyn` is an ordinary `Arc`, so it can be held by an [`AtomicArc`] or a
+/// [`Snapshot`], and it derefs to the trait object. Use [`arc_dyn!`] to create one.
+///
+/// [`AtomicArc`]: `crate::AtomicArc`
+/// [`arc_dyn!`]: `crate::arc_dyn`
+#[cfg(not(feature = "std"))]
+pub type ArcDyn<D, R> = Arc<Box<D>, R>;
+
+/// Creates an [`ArcDyn`] by moving a value into a trait object.
+///
+/// `arc_dyn!(value => dyn Trait)` names the trait object type and uses the default reclaimer.
+/// `arc_dyn!(value)` infers both the trait object type and the reclaimer from the context.
+///
+/// # Examples
+/// ```
+/// use std::fmt::Display;
+/// use aarc::{arc_dyn, ArcDyn};
+///
+/// let x = arc_dyn!(53 => dyn Display + Send + Sync);
+/// assert_eq!(x.to_string(), "53");
+///
+/// let y: ArcDyn<dyn Display + Send + Sync> = arc_dyn!("fifty-three");
+/// assert_eq!(y.to_string(), "fifty-three");
+/// ```
+#[macro_export]
+macro_rules! arc_dyn {
+    ($value:expr => $ty:ty) => {
+        $crate::Arc::<$crate::__private::Box<$ty>>::new_in(
+            $crate::__private::Box::new($value) as $crate::__private::Box<$ty>
+        )
+    };
+    ($value:expr) => {
+        $crate::Arc::<$crate::__private::Box<_>, _>::new_in($crate::__private::Box::new($value) as _)
+    };
+}
+
+default_reclaimer! {
+    /// A reimplementation of [`std::sync::Weak`].
+    ///
+    /// See [`Arc`] for details on how this struct differs from the standard library's.
+    ///
+    /// See [`std::sync::Weak`] for per-method documentation.
+    pub struct Weak<T: 'static, R: Retire> {
+        ptr: NonNull<ArcInner<T>>,
+        phantom_r: PhantomData<R>,
+    }
 }
 
 impl<T: 'static, R: Retire> Weak<T, R> {
-    #[allow(clippy::missing_safety_doc)]
+    /// Reconstructs a `Weak` from a pointer returned by [`Weak::into_raw`], taking over the weak
+    /// reference that it carried.
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by `into_raw` on a `Weak<T, R>` with the same `T` and `R`, and
+    /// each such pointer may only be converted back once. As with [`Arc::from_raw`], the layout of
+    /// the allocation is unspecified.
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         Self {
             ptr: NonNull::new_unchecked(ptr as *mut ArcInner<T>),
@@ -161,11 +686,47 @@ impl<T: 'static, R: Retire> Weak<T, R> {
     pub(crate) unsafe fn increment_weak_count(ptr: *const T) {
         (*(ptr as *const ArcInner<T>)).increment_weak_count();
     }
+    /// Returns a raw pointer to the value, without affecting the reference counts. Unlike
+    /// [`Arc::as_ptr`], the value may already have been dropped, in which case the pointer must not
+    /// be dereferenced, but the allocation remains valid for as long as the `Weak` does.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::Arc;
+    ///
+    /// let x = Arc::new(53);
+    /// let w = Arc::downgrade(&x);
+    /// assert_eq!(w.as_ptr(), Arc::as_ptr(&x));
+    /// ```
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr() as *const T
+    }
+    /// Consumes the `Weak` and returns a pointer to the value, which carries its weak reference.
+    /// Use [`Weak::from_raw`] to convert it back and release the reference.
     pub fn into_raw(self) -> *const T {
-        let ptr = Self::as_ptr(&self);
+        let ptr = self.as_ptr();
         mem::forget(self);
         ptr
     }
+    /// Returns the number of `Arc`s pointing to this allocation, or 0 if the value has been
+    /// dropped (or is about to be).
+    pub fn strong_count(&self) -> usize {
+        unsafe { (*self.ptr.as_ptr()).strong.load(Relaxed) }
+    }
+    /// Returns the number of `Weak`s pointing to this allocation.
+    ///
+    /// Unlike [`std::sync::Weak::weak_count`], this may overestimate by one after the strong count
+    /// reaches 0: the `Arc`s collectively hold an implicit weak reference, which is only released
+    /// once the reclaimer destroys the value. The count is exact again after that.
+    pub fn weak_count(&self) -> usize {
+        let inner = unsafe { &*self.ptr.as_ptr() };
+        let weak = inner.weak_count(Relaxed);
+        if inner.strong.load(Relaxed) == 0 {
+            weak
+        } else {
+            weak - 1
+        }
+    }
     pub fn upgrade(&self) -> Option<Arc<T, R>> {
         unsafe {
             (*self.ptr.as_ptr())
@@ -181,17 +742,46 @@ impl<T: 'static, R: Retire> Weak<T, R> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: 'static + Serialize, R: Retire> Serialize for Arc<T, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: 'static + Deserialize<'de>, R: Retire> Deserialize<'de> for Arc<T, R> {
+    /// Deserializes a `T` into a new allocation.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Arc::new_in)
+    }
+}
+
+impl<T: 'static, R: Retire> Debug for Weak<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "(Weak)")
+    }
+}
+
 impl<T: 'static, R: Retire> Drop for Weak<T, R> {
     fn drop(&mut self) {
         unsafe {
             let inner = self.ptr.as_ptr();
-            if (*inner).weak.fetch_sub(1, SeqCst) == 1 {
+            if (*inner).weak.fetch_sub(1, SeqCst) & !TRAILING == 1 {
                 fence(Acquire);
                 R::retire(
                     inner as *mut u8,
                     Box::new(move || {
-                        if (*inner).weak.load(SeqCst) == 0 {
-                            dealloc(inner as *mut u8, Layout::new::<ArcInner<T>>())
+                        let weak = (*inner).weak.load(SeqCst);
+                        if weak & !TRAILING == 0 {
+                            let layout = if weak == TRAILING {
+                                ptr::read(inner as *const Layout)
+                            } else {
+                                Layout::new::<ArcInner<T>>()
+                            };
+                            dealloc(inner as *mut u8, layout);
+                            #[cfg(debug_assertions)]
+                            LIVE_ALLOCATIONS.fetch_sub(1, Relaxed);
                         }
                     }),
                 );
@@ -204,36 +794,252 @@ unsafe impl<T: 'static + Send + Sync, R: Retire> Send for Weak<T, R> {}
 
 unsafe impl<T: 'static + Send + Sync, R: Retire> Sync for Weak<T, R> {}
 
-/// An [`Arc`]-like pointer that facilitates reads and writes to [`AtomicArc`] and [`AtomicWeak`].
-///
-/// Like [`Arc`], `Snapshot` provides an immutable reference `&T` and prevents deallocation, but
-/// it does *not* affect reference counts.
-///
-/// Consider, for example, the process of traversing a tree or linked list. If an [`Arc`] (instead
-/// of a `Snapshot`) were loaded from each [`AtomicArc`], every visit to a node would be sandwiched
-/// by an increment and a decrement. `Snapshot`s eliminate this contention.
-///
-/// A `Snapshot` should be used as a temporary variable. **It should not be used in place of
-/// [`Arc`] or [`AtomicArc`] in a data structure**. In addition, if a thread holds too
-/// many `Snapshot`s at a time, the performance of [`StandardReclaimer`] may gradually degrade.
-///
-/// The only way to obtain one is to `load` an [`AtomicArc`] or `upgrade` an [`AtomicWeak`].
-///
-/// [`AtomicArc`]: `super::AtomicArc`
-/// [`AtomicWeak`]: `super::AtomicWeak`
-pub struct Snapshot<T: 'static, R: ProtectPtr = StandardReclaimer> {
-    ptr: NonNull<ArcInner<T>>,
-    phantom: PhantomData<ArcInner<T>>,
-    handle: &'static R::ProtectionHandle,
+default_reclaimer! {
+    /// An [`Arc`]-like pointer that facilitates reads and writes to [`AtomicArc`] and [`AtomicWeak`].
+    ///
+    /// Like [`Arc`], `Snapshot` provides an immutable reference `&T` and prevents deallocation, but
+    /// it does *not* affect reference counts.
+    ///
+    /// Consider, for example, the process of traversing a tree or linked list. If an [`Arc`] (instead
+    /// of a `Snapshot`) were loaded from each [`AtomicArc`], every visit to a node would be sandwiched
+    /// by an increment and a decrement. `Snapshot`s eliminate this contention.
+    ///
+    /// A `Snapshot` should be used as a temporary variable. **It should not be used in place of
+    /// [`Arc`] or [`AtomicArc`] in a data structure**. In addition, if a thread holds too
+    /// many `Snapshot`s at a time, the performance of [`StandardReclaimer`] may gradually degrade.
+    /// To bound this, a thread can [limit][`TunableReclaimer::set_snapshots_limit`] its
+    /// protections, past which new `Snapshot`s increment the strong count instead.
+    ///
+    /// As its protection belongs to the current thread, a `Snapshot` is not [`Send`]. To process a
+    /// value on another thread, `load` an [`Arc`] instead, or convert a `Snapshot` with
+    /// `Arc::from`. The strong count is incremented while the pointer is still protected, so unlike
+    /// upgrading a [`Weak`], this can't fail because of a concurrent store. It is heavier than a
+    /// `Snapshot`, but can be moved across threads.
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use std::thread;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// let atomic = AtomicArc::new(Some(53));
+    /// let snapshot = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+    /// let arc: Arc<i32> = Arc::from(&snapshot);
+    /// atomic.store(None::<&Arc<_>>, SeqCst);
+    /// thread::spawn(move || assert_eq!(*arc, 53)).join().unwrap();
+    /// ```
+    ///
+    /// A `Snapshot` has no lifetime parameter because its protection does not depend on a borrow:
+    /// it keeps the value alive until it is dropped, even if the [`AtomicArc`] it was loaded from
+    /// is overwritten or dropped in the meantime. It can therefore be returned from a method that
+    /// borrows the data structure. To tie it to a shorter scope, take a reference `&Snapshot`,
+    /// which the borrow checker confines as usual.
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{AtomicArc, Snapshot};
+    ///
+    /// struct Config {
+    ///     current: AtomicArc<String>,
+    /// }
+    ///
+    /// impl Config {
+    ///     fn get(&self) -> Option<Snapshot<String>> {
+    ///         self.current.load(SeqCst)
+    ///     }
+    /// }
+    ///
+    /// let config = Config { current: AtomicArc::new(Some("a".to_string())) };
+    /// let snapshot = config.get().unwrap();
+    /// drop(config);
+    /// assert_eq!(*snapshot, "a");
+    /// ```
+    ///
+    /// A `Snapshot` is obtained by `load`ing an [`AtomicArc`], `upgrade`ing an [`AtomicWeak`], or
+    /// converting an [`Arc`] with `Snapshot::from`.
+    ///
+    /// [`AtomicArc`]: `super::AtomicArc`
+    /// [`AtomicWeak`]: `super::AtomicWeak`
+    /// [`TunableReclaimer::set_snapshots_limit`]: `crate::smr::standard_reclaimer::TunableReclaimer::set_snapshots_limit`
+    pub struct Snapshot<T: 'static, R: ProtectPtr + Retire> {
+        ptr: NonNull<ArcInner<T>>,
+        phantom: PhantomData<ArcInner<T>>,
+        // If `None`, the snapshot holds a strong count instead of a protection.
+        handle: Option<&'static R::ProtectionHandle>,
+    }
 }
 
-impl<T: 'static, R: ProtectPtr> Clone for Snapshot<T, R> {
+impl<T: 'static, R: ProtectPtr + Retire> Snapshot<T, R> {
+    /// Returns `true` if `this` and `other` (an [`Arc`], a `Snapshot`, or a [`Weak`]) point to the
+    /// same allocation. Unlike comparing with `==`, this compares addresses, not values.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// let arc = Arc::new(53);
+    /// let atomic = AtomicArc::from(&arc);
+    /// let snapshot = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+    /// assert!(Snapshot::ptr_eq(&snapshot, &arc));
+    /// assert!(Snapshot::ptr_eq(&snapshot, &snapshot.clone()));
+    /// assert!(!Snapshot::ptr_eq(&snapshot, &Arc::new(53)));
+    /// ```
+    pub fn ptr_eq<P: AsPtr<T>>(this: &Self, other: &P) -> bool {
+        ptr::eq(Self::as_ptr(this), P::as_ptr(other))
+    }
+    /// Releases a group of `Snapshot`s at once.
+    ///
+    /// This is equivalent to dropping each `Snapshot` individually, but it allows the reclaimer to
+    /// amortize the cost of releasing the protections, which is useful after a traversal that
+    /// accumulated many `Snapshot`s.
+    pub fn release_all<I: IntoIterator<Item = Self>>(snapshots: I) {
+        let handles: Vec<_> = snapshots
+            .into_iter()
+            .filter_map(|snapshot| {
+                // Snapshots that hold strong counts are simply dropped.
+                let handle = snapshot.handle?;
+                mem::forget(snapshot);
+                Some(handle)
+            })
+            .collect();
+        R::ProtectionHandle::release_many(&handles);
+    }
+    /// Returns a new [`Arc`] to the value, or [`None`] if its strong count has already reached
+    /// zero.
+    ///
+    /// A `Snapshot` keeps the memory valid, but not necessarily the value alive: one obtained from
+    /// an [`AtomicWeak`] may outlive the last `Arc`. Unlike `Arc::from`, which always increments
+    /// the strong count (reviving the value if necessary), this only succeeds while at least one
+    /// other strong reference exists.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicWeak, Snapshot};
+    ///
+    /// let arc = Arc::new(53);
+    /// let atomic = AtomicWeak::from(&arc);
+    /// let snapshot = atomic.upgrade::<Snapshot<_>>(SeqCst).unwrap();
+    /// assert_eq!(*snapshot.try_upgrade().unwrap(), 53);
+    ///
+    /// drop(arc);
+    /// assert!(snapshot.try_upgrade().is_none());
+    /// ```
+    ///
+    /// [`AtomicWeak`]: `super::AtomicWeak`
+    pub fn try_upgrade(&self) -> Option<Arc<T, R>> {
+        unsafe { Arc::try_clone_from_raw(Self::as_ptr(self)) }
+    }
+    /// Returns a mutable reference to the value without any check, as with
+    /// [`Arc::get_mut_unchecked`].
+    ///
+    /// # Safety
+    /// No other `Snapshot`, [`Arc`], [`Weak`], or atomic may be used to access the value for the
+    /// duration of the returned borrow. This holds, for example, for a node that a single writer
+    /// has just loaded and that no other thread has been given a path to yet.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{AtomicArc, Snapshot};
+    ///
+    /// let atomic = AtomicArc::new(Some(Vec::new()));
+    /// let mut snapshot = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+    /// unsafe { Snapshot::get_mut_unchecked(&mut snapshot).push(53) };
+    /// assert_eq!(*snapshot, [53]);
+    /// ```
+    pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
+        &mut (*this.ptr.as_ptr()).data
+    }
+    /// Releases the `Snapshot` and returns its pointer, e.g. to pass it through foreign code as
+    /// an opaque handle.
+    ///
+    /// Unlike [`Arc::into_raw`], this does not keep anything alive: the protection is released
+    /// immediately, so the pointer may be reclaimed at any time unless something else (such as
+    /// an `Arc` or an [`AtomicArc`] that is known not to change) keeps it alive. Use
+    /// [`Snapshot::from_raw`] to protect it again.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::{Arc, Snapshot};
+    ///
+    /// let arc = Arc::new(53);
+    /// let ptr = Snapshot::into_raw(Snapshot::from(&arc));
+    /// // The Arc keeps the value alive while the pointer is unprotected.
+    /// let snapshot: Snapshot<_> = unsafe { Snapshot::from_raw(ptr) };
+    /// assert_eq!(*snapshot, 53);
+    /// ```
+    ///
+    /// [`AtomicArc`]: `super::AtomicArc`
+    pub fn into_raw(this: Self) -> *const T {
+        Self::as_ptr(&this)
+    }
+    /// Creates a `Snapshot` from a pointer returned by [`Snapshot::into_raw`] (or
+    /// [`Arc::into_raw`]), protecting it anew.
+    ///
+    /// # Safety
+    /// The value must not have been reclaimed since the pointer was obtained. The protection was
+    /// released by `into_raw`, so this must be guaranteed by another pointer that kept the value
+    /// alive in the meantime.
+    pub unsafe fn from_raw(ptr: *const T) -> Self {
+        Self::clone_from_raw(ptr)
+    }
+    /// Protects `ptr`, or increments its strong count if the reclaimer declines to protect it
+    /// (see [`ProtectPtr::try_protect_ptr`]), in which case `None` is returned.
+    unsafe fn protect(ptr: *const T) -> Option<&'static R::ProtectionHandle> {
+        match R::try_protect_ptr(ptr as *mut u8) {
+            Some(handle) => Some(handle),
+            None if Arc::<T, R>::try_increment_strong_count(ptr) => None,
+            // A value whose strong count has reached zero can only be kept alive by a protection.
+            None => Some(R::protect_ptr(ptr as *mut u8)),
+        }
+    }
+}
+
+impl<T: 'static, R: ProtectPtr + Retire> Clone for Snapshot<T, R> {
     fn clone(&self) -> Self {
         unsafe { Self::clone_from_raw(Self::as_ptr(self)) }
     }
 }
 
-impl<T: 'static, R: ProtectPtr> Deref for Snapshot<T, R> {
+impl<T: 'static + Debug, R: ProtectPtr + Retire> Debug for Snapshot<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: 'static + Serialize, R: ProtectPtr + Retire> Serialize for Snapshot<T, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T: 'static + PartialEq, R: ProtectPtr + Retire> PartialEq for Snapshot<T, R> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: 'static + Eq, R: ProtectPtr + Retire> Eq for Snapshot<T, R> {}
+
+impl<T: 'static + PartialOrd, R: ProtectPtr + Retire> PartialOrd for Snapshot<T, R> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: 'static + Ord, R: ProtectPtr + Retire> Ord for Snapshot<T, R> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: 'static + Hash, R: ProtectPtr + Retire> Hash for Snapshot<T, R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T: 'static, R: ProtectPtr + Retire> Deref for Snapshot<T, R> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -241,12 +1047,97 @@ impl<T: 'static, R: ProtectPtr> Deref for Snapshot<T, R> {
     }
 }
 
-impl<T: 'static, R: ProtectPtr> Drop for Snapshot<T, R> {
+impl<T: 'static, R: ProtectPtr + Retire> AsRef<T> for Snapshot<T, R> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: 'static, R: ProtectPtr + Retire> Borrow<T> for Snapshot<T, R> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T: 'static, R: ProtectPtr + Retire> Drop for Snapshot<T, R> {
+    fn drop(&mut self) {
+        match self.handle {
+            Some(handle) => handle.release(),
+            None => unsafe { drop(Arc::<T, R>::from_raw(Self::as_ptr(self))) },
+        }
+    }
+}
+
+/// The error returned by [`Arc::try_new`] and similar constructors when the allocator fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
+
+/// A value that is passed to a function instead of being dropped. See
+/// [`Arc::new_with_reclaim_in`].
+pub struct Reclaimed<T> {
+    data: ManuallyDrop<T>,
+    on_reclaim: fn(*mut T),
+}
+
+impl<T> Reclaimed<T> {
+    pub fn new(data: T, on_reclaim: fn(*mut T)) -> Self {
+        Self {
+            data: ManuallyDrop::new(data),
+            on_reclaim,
+        }
+    }
+    /// Returns the value without passing it to `on_reclaim`.
+    pub fn into_inner(this: Self) -> T {
+        let mut this = ManuallyDrop::new(this);
+        unsafe { ManuallyDrop::take(&mut this.data) }
+    }
+}
+
+impl<T> Deref for Reclaimed<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<T> DerefMut for Reclaimed<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+impl<T: Debug> Debug for Reclaimed<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Drop for Reclaimed<T> {
     fn drop(&mut self) {
-        self.handle.release();
+        (self.on_reclaim)(&mut *self.data);
     }
 }
 
+/// Shared by every `Arc` of a zero-sized type that doesn't need to be dropped, so that creating one
+/// doesn't allocate. The counts start out saturated, so they never reach zero.
+#[cfg(not(loom))]
+static ZST_INNER: ArcInner<()> = ArcInner {
+    data: (),
+    strong: AtomicUsize::new(usize::MAX / 2),
+    weak: AtomicUsize::new(usize::MAX / 2),
+};
+
+// The value comes first, so that a pointer to the value is also a pointer to the `ArcInner`. This
+// is what lets the raw pointers of the public types point directly at the value.
 #[repr(C)]
 pub(crate) struct ArcInner<T> {
     data: T,
@@ -254,7 +1145,17 @@ pub(crate) struct ArcInner<T> {
     weak: AtomicUsize,
 }
 
+/// Set in the weak count of an allocation that extends past its `ArcInner`, e.g. to hold the
+/// elements of an [`ArcSlice`]. The value then starts with the [`Layout`] of the whole allocation,
+/// which is read back to free it, since `Layout::new::<ArcInner<T>>()` would be too small.
+///
+/// [`ArcSlice`]: `crate::ArcSlice`
+const TRAILING: usize = 1 << (usize::BITS - 1);
+
 impl<T> ArcInner<T> {
+    fn weak_count(&self, order: Ordering) -> usize {
+        self.weak.load(order) & !TRAILING
+    }
     pub(crate) fn increment_strong_count(&self) {
         self.strong.fetch_add(1, Relaxed);
     }
@@ -271,17 +1172,17 @@ pub trait AsPtr<T> {
 
 impl<T: 'static, R: Retire> AsPtr<T> for Arc<T, R> {
     fn as_ptr(this: &Self) -> *const T {
-        this.ptr.as_ptr() as *const T
+        Arc::as_ptr(this)
     }
 }
 
 impl<T: 'static, R: Retire> AsPtr<T> for Weak<T, R> {
     fn as_ptr(this: &Self) -> *const T {
-        this.ptr.as_ptr() as *const T
+        Weak::as_ptr(this)
     }
 }
 
-impl<T: 'static, R: ProtectPtr> AsPtr<T> for Snapshot<T, R> {
+impl<T: 'static, R: ProtectPtr + Retire> AsPtr<T> for Snapshot<T, R> {
     fn as_ptr(this: &Self) -> *const T {
         this.ptr.as_ptr() as *const T
     }
@@ -305,12 +1206,12 @@ impl<T: 'static, R: Retire> CloneFromRaw<T> for Weak<T, R> {
     }
 }
 
-impl<T: 'static, R: ProtectPtr> CloneFromRaw<T> for Snapshot<T, R> {
+impl<T: 'static, R: ProtectPtr + Retire> CloneFromRaw<T> for Snapshot<T, R> {
     unsafe fn clone_from_raw(ptr: *const T) -> Self {
         Self {
             ptr: NonNull::new_unchecked(ptr as *mut ArcInner<T>),
             phantom: PhantomData,
-            handle: R::protect_ptr(ptr as *mut u8),
+            handle: Self::protect(ptr),
         }
     }
 }
@@ -321,15 +1222,15 @@ pub trait TryCloneFromRaw<T>: Sized {
 
 impl<T: 'static, R: Retire> TryCloneFromRaw<T> for Arc<T, R> {
     unsafe fn try_clone_from_raw(ptr: *const T) -> Option<Self> {
-        Self::try_increment_strong_count(ptr).then_some(Self::from_raw(ptr))
+        Self::try_increment_strong_count(ptr).then(|| Self::from_raw(ptr))
     }
 }
 
-impl<T: 'static, R: ProtectPtr> TryCloneFromRaw<T> for Snapshot<T, R> {
+impl<T: 'static, R: ProtectPtr + Retire> TryCloneFromRaw<T> for Snapshot<T, R> {
     unsafe fn try_clone_from_raw(ptr: *const T) -> Option<Self> {
         let inner = ptr as *mut ArcInner<T>;
-        let handle = R::protect_ptr(ptr as *mut u8);
-        if (*inner).strong.load(SeqCst) == 0 {
+        let handle = Self::protect(ptr);
+        if let Some(handle) = handle.filter(|_| (*inner).strong.load(SeqCst) == 0) {
             handle.release();
             return None;
         }
@@ -341,6 +1242,20 @@ impl<T: 'static, R: ProtectPtr> TryCloneFromRaw<T> for Snapshot<T, R> {
     }
 }
 
+impl<T: 'static, R: Retire> From<T> for Arc<T, R> {
+    fn from(value: T) -> Self {
+        Self::new_in(value)
+    }
+}
+
+impl<T: 'static, R: Retire> From<Box<T>> for Arc<T, R> {
+    /// Moves the boxed value into a new `Arc`. The `Box`'s allocation can't be reused, as it has
+    /// no room for the reference counts, so the value is copied and the `Box` is deallocated.
+    fn from(value: Box<T>) -> Self {
+        Self::new_in(*value)
+    }
+}
+
 impl<T: 'static, R: ProtectPtr + Retire> From<&Arc<T, R>> for Snapshot<T, R> {
     fn from(value: &Arc<T, R>) -> Self {
         unsafe { Self::clone_from_raw(Arc::as_ptr(value)) }
@@ -355,9 +1270,15 @@ impl<T: 'static, R: ProtectPtr + Retire> From<&Snapshot<T, R>> for Arc<T, R> {
 
 #[cfg(test)]
 mod tests {
-    use crate::smr::standard_reclaimer::StandardReclaimer;
-    use crate::{Arc, Weak};
+    use crate::smr::standard_reclaimer::{IsolatedReclaimer, StandardReclaimer};
+    use crate::utils::test_utils::DropCounter;
+    use crate::{AllocError, Arc, ArcDyn, AsPtr, AtomicArc, Reclaimed, Snapshot, Weak};
+    use std::borrow::Borrow;
     use std::cell::RefCell;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::atomic::Ordering::SeqCst;
+    use std::sync::atomic::{AtomicBool, AtomicUsize};
+    use std::{hint, thread};
 
     #[test]
     fn test_arc_cascading_drop() {
@@ -372,6 +1293,296 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_arc_deep_cascading_drop() {
+        struct Node {
+            _next: Option<Arc<Self>>,
+            _counter: DropCounter<Node>,
+        }
+        // Each node is only retired once its predecessor is reclaimed, so dropping many chains
+        // at once fills batch after batch from within the destructors of the previous one.
+        const CHAINS: usize = 64;
+        const DEPTH: usize = 10_000;
+        let heads: Vec<_> = (0..CHAINS)
+            .map(|_| {
+                let mut head = None;
+                for _ in 0..DEPTH {
+                    head = Some(Arc::new(Node {
+                        _next: head,
+                        _counter: DropCounter::new(),
+                    }));
+                }
+                head.unwrap()
+            })
+            .collect();
+        Arc::drop_batch(heads);
+        while DropCounter::<Node>::drops() < CHAINS * DEPTH {
+            StandardReclaimer::flush();
+        }
+    }
+
+    #[test]
+    fn test_arc_panicking_drop() {
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker>;
+        struct Panics;
+        impl Drop for Panics {
+            fn drop(&mut self) {
+                panic!("intentional panic in drop");
+            }
+        }
+        drop(Arc::<_, Reclaimer>::new_in(Panics));
+        drop(Arc::<_, Reclaimer>::new_in(DropCounter::<Marker>::new()));
+        Reclaimer::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 1);
+    }
+
+    #[test]
+    fn test_arc_try_unwrap() {
+        let x = Arc::new(53);
+        let y = x.clone();
+        let x = Arc::try_unwrap(x).unwrap_err();
+        assert_eq!(Arc::strong_count(&x), 2);
+        drop(y);
+
+        let snapshot = Snapshot::from(&x);
+        let x = Arc::try_unwrap(x).unwrap_err();
+        assert_eq!(Arc::strong_count(&x), 1);
+        assert_eq!(*snapshot, 53);
+    }
+
+    #[test]
+    fn test_arc_try_unwrap_with_concurrent_upgrade() {
+        let x = Arc::new(53);
+        let weak = Arc::downgrade(&x);
+        // The snapshot keeps `try_unwrap` failing even while the upgraded `Arc`s are dropped.
+        let snapshot = Snapshot::from(&x);
+        let started = AtomicBool::new(false);
+        thread::scope(|s| {
+            let upgrader = s.spawn(|| {
+                while !started.load(SeqCst) {
+                    hint::spin_loop();
+                }
+                for _ in 0..1000 {
+                    assert_eq!(weak.upgrade().as_deref(), Some(&53));
+                }
+            });
+            let mut x = x;
+            started.store(true, SeqCst);
+            while !upgrader.is_finished() {
+                x = Arc::try_unwrap(x).unwrap_err();
+            }
+        });
+        drop(snapshot);
+    }
+
+    #[test]
+    fn test_arc_make_mut() {
+        let mut x = Arc::new(53);
+        let y = x.clone();
+        assert!(Arc::get_mut(&mut x).is_none());
+        *Arc::make_mut(&mut x) += 1;
+        assert_eq!((*x, *y), (54, 53));
+        assert!(!Arc::ptr_eq(&x, &y));
+
+        let mut z = y.clone();
+        let snapshot = Snapshot::from(&y);
+        drop(y);
+        assert!(Arc::get_mut(&mut z).is_none());
+        *Arc::make_mut(&mut z) += 2;
+        assert_eq!((*z, *snapshot), (55, 53));
+
+        let w = Arc::downgrade(&x);
+        assert!(Arc::get_mut(&mut x).is_none());
+        drop(w);
+    }
+
+    #[test]
+    fn test_arc_value_comparisons() {
+        let mut arcs = [Arc::new(3), Arc::new(1), Arc::new(2)];
+        arcs.sort();
+        assert_eq!(arcs.iter().map(|x| **x).collect::<Vec<_>>(), [1, 2, 3]);
+
+        let x = Arc::new(1);
+        assert_eq!(x, arcs[0]);
+        assert!(!Arc::ptr_eq(&x, &arcs[0]));
+        assert!(Snapshot::from(&x) < Snapshot::from(&arcs[1]));
+
+        let set: HashSet<_> = [Arc::new(1), x.clone(), Arc::new(2)].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_arc_borrow() {
+        let key = Arc::new(String::from("aarc"));
+        let mut map = HashMap::new();
+        map.insert(key.clone(), 53);
+        assert_eq!(map.get(&String::from("aarc")), Some(&53));
+
+        fn len<S: AsRef<String>>(s: S) -> usize {
+            s.as_ref().len()
+        }
+        fn is_aarc<B: Borrow<String>>(b: B) -> bool {
+            b.borrow() == "aarc"
+        }
+        assert_eq!(len(&key), 4);
+        assert_eq!(len(Snapshot::from(&key)), 4);
+        assert!(is_aarc(Snapshot::from(&key)));
+    }
+
+    #[test]
+    fn test_arc_default() {
+        #[derive(Default)]
+        struct Registry {
+            names: Arc<Vec<String>>,
+        }
+
+        let registry = Registry::default();
+        assert!(registry.names.is_empty());
+
+        let mut map: HashMap<u32, Arc<usize>> = HashMap::new();
+        assert_eq!(**map.entry(0).or_default(), 0);
+    }
+
+    #[test]
+    fn test_arc_from() {
+        let x: Arc<_> = 53.into();
+        assert_eq!(*x, 53);
+        let y: Arc<String> = Box::new(String::from("aarc")).into();
+        assert_eq!(*y, "aarc");
+    }
+
+    #[test]
+    fn test_arc_try_new() {
+        assert_eq!(*Arc::try_new(53).unwrap(), 53);
+    }
+
+    #[test]
+    // The size of the value can't be large enough to fail reliably on 32-bit platforms, and Miri
+    // reports an oversized allocation as an error rather than failing it.
+    #[cfg(target_pointer_width = "64")]
+    #[cfg_attr(miri, ignore)]
+    fn test_arc_try_new_failure() {
+        assert_eq!(
+            Arc::<[u8; 1 << 47]>::try_new_uninit().err(),
+            Some(AllocError)
+        );
+    }
+
+    #[test]
+    fn test_arc_zst_shares_allocation() {
+        let x = Arc::new(());
+        let y = Arc::new(());
+        assert!(Arc::ptr_eq(&x, &y));
+        let mut z = x.clone();
+        assert!(Arc::get_mut(&mut z).is_none());
+        drop((x, z));
+
+        let atomic = AtomicArc::from(&y);
+        assert!(atomic.load::<Snapshot<_>>(SeqCst).is_some());
+        drop((atomic, y));
+
+        // A zero-sized type with a destructor still gets its own allocation, so it is dropped.
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker>;
+        let x = Arc::<_, Reclaimer>::new_in(DropCounter::<Marker>::new());
+        assert!(!Arc::ptr_eq(&x, &Arc::new_in(DropCounter::<Marker>::new())));
+        assert_eq!(Arc::try_unwrap(x).ok().map(drop), Some(()));
+        Reclaimer::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 2);
+    }
+
+    #[test]
+    fn test_arc_new_with_reclaim() {
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker>;
+        static RECLAIMS: AtomicUsize = AtomicUsize::new(0);
+        let on_reclaim = |_: *mut ()| _ = RECLAIMS.fetch_add(1, SeqCst);
+
+        let x = Arc::<_, Reclaimer>::new_with_reclaim_in((), on_reclaim);
+        let weak = Arc::downgrade(&x);
+        drop(x);
+        Reclaimer::flush();
+        assert_eq!(RECLAIMS.load(SeqCst), 1);
+        assert!(weak.upgrade().is_none());
+
+        let y = Arc::<_, Reclaimer>::new_with_reclaim_in((), on_reclaim);
+        assert_eq!(Arc::try_unwrap(y).ok().map(Reclaimed::into_inner), Some(()));
+        Reclaimer::flush();
+        assert_eq!(RECLAIMS.load(SeqCst), 1);
+    }
+
+    #[test]
+    fn test_arc_dyn() {
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker>;
+        type Callback = dyn Fn(usize) -> usize + Send + Sync;
+
+        let double: ArcDyn<Callback, Reclaimer> = arc_dyn!(|x: usize| x * 2);
+        let atomic = AtomicArc::from(&double);
+        assert_eq!(atomic.load::<Snapshot<_>>(SeqCst).unwrap()(4), 8);
+
+        let offset = 3;
+        let add: ArcDyn<Callback, Reclaimer> = arc_dyn!(move |x: usize| x + offset);
+        atomic.store(Some(&add), SeqCst);
+        assert_eq!(atomic.load::<Arc<_, Reclaimer>>(SeqCst).unwrap()(4), 7);
+        assert_eq!(double(4), 8);
+        drop(atomic);
+        Reclaimer::flush();
+        assert_eq!(Arc::strong_count(&double), 1);
+
+        let s = arc_dyn!(String::from("53") => dyn AsRef<str> + Send + Sync);
+        assert_eq!((**s).as_ref(), "53");
+    }
+
+    #[test]
+    fn test_arc_over_aligned() {
+        #[repr(align(64))]
+        struct Line(u8);
+        #[repr(align(4096))]
+        struct Page([u8; 8]);
+        #[repr(align(64))]
+        struct Empty;
+
+        fn is_aligned<T, P: AsPtr<T>>(p: &P) -> bool {
+            P::as_ptr(p).is_aligned()
+        }
+
+        let lines: Vec<_> = (0..8).map(|i| Arc::new(Line(i))).collect();
+        assert!(lines.iter().all(is_aligned));
+        assert!(lines.iter().enumerate().all(|(i, line)| line.0 == i as u8));
+
+        let page = Arc::new(Page([7; 8]));
+        assert!(is_aligned(&page));
+        assert!(is_aligned(&Arc::downgrade(&page)));
+        let atomic = AtomicArc::from(&page);
+        let snapshot = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+        assert!(is_aligned(&snapshot));
+        assert_eq!(snapshot.0, [7; 8]);
+
+        let zeroed = unsafe { Arc::<Page>::new_zeroed().assume_init() };
+        assert!(is_aligned(&zeroed));
+        assert_eq!(zeroed.0, [0; 8]);
+
+        // Over-aligned zero-sized types can't share the static allocation.
+        assert!(is_aligned(&Arc::new(Empty)));
+    }
+
+    #[test]
+    fn test_arc_drop_batch() {
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker>;
+        let arcs: Vec<_> = (0..10)
+            .map(|_| Arc::<_, Reclaimer>::new_in(DropCounter::<Marker>::new()))
+            .collect();
+        let kept = arcs[0].clone();
+        Arc::drop_batch(arcs);
+        assert_eq!(Arc::strong_count(&kept), 1);
+        Reclaimer::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 9);
+    }
+
     #[test]
     fn test_arc_weak_cycle() {
         struct Node {
@@ -391,4 +1602,104 @@ mod tests {
             StandardReclaimer::cleanup();
         }
     }
+
+    #[test]
+    fn test_arc_is_unique_and_is_shared() {
+        let x = Arc::new(53);
+        assert!(Arc::is_unique(&x));
+        assert!(!Arc::is_shared(&x));
+
+        let y = x.clone();
+        assert!(!Arc::is_unique(&x));
+        assert!(Arc::is_shared(&y));
+        drop(y);
+
+        let w = Arc::downgrade(&x);
+        assert!(!Arc::is_unique(&x));
+        assert!(Arc::is_shared(&x));
+        drop(w);
+
+        assert!(Arc::is_unique(&x));
+        assert!(!Arc::is_shared(&x));
+    }
+
+    #[test]
+    fn test_arc_new_uninit() {
+        let mut x = Arc::<[usize; 4]>::new_uninit();
+        let x = unsafe {
+            Arc::get_mut_unchecked(&mut x).write([1, 2, 3, 4]);
+            x.assume_init()
+        };
+        assert_eq!(*x, [1, 2, 3, 4]);
+        let w = Arc::downgrade(&x);
+        drop(x);
+        assert!(w.upgrade().is_none());
+
+        let y = unsafe { Arc::<usize>::new_zeroed().assume_init() };
+        assert_eq!(*y, 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_arc_serde() {
+        let x = Arc::new(vec![1, 2, 3]);
+        let json = serde_json::to_string(&x).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        let y: Arc<Vec<i32>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(x, y);
+        assert!(!Arc::ptr_eq(&x, &y));
+    }
+
+    #[test]
+    fn test_weak_counts_after_cleanup() {
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker>;
+        let x = Arc::<_, Reclaimer>::new_in(53);
+        let w = Arc::downgrade(&x);
+        let w2 = Arc::downgrade(&x);
+        assert_eq!(w.strong_count(), 1);
+        assert_eq!(w.weak_count(), 2);
+        assert_eq!(Arc::weak_count(&x), 2);
+
+        let s = Snapshot::from(&x);
+        drop(x);
+        assert_eq!(w.strong_count(), 0);
+        // The implicit weak reference is held until the value is reclaimed, which the snapshot
+        // prevents.
+        Reclaimer::flush();
+        assert_eq!(w.weak_count(), 3);
+        drop(s);
+        Reclaimer::flush();
+        assert_eq!(w.weak_count(), 2);
+        drop(w2);
+        assert_eq!(w.weak_count(), 1);
+    }
+
+    #[test]
+    fn test_weak_raw_round_trip() {
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker>;
+        let round_trip = |w: Weak<DropCounter<Marker>, Reclaimer>| {
+            let ptr = w.as_ptr();
+            let raw = w.into_raw();
+            assert_eq!(raw, ptr);
+            unsafe { Weak::<_, Reclaimer>::from_raw(raw) }
+        };
+
+        let x = Arc::<_, Reclaimer>::new_in(DropCounter::<Marker>::new());
+        let w = round_trip(Arc::downgrade(&x));
+        assert_eq!((w.strong_count(), w.weak_count()), (1, 1));
+        assert!(w.upgrade().is_some());
+
+        // The value is dropped, but the `Weak` keeps the allocation alive.
+        drop(x);
+        Reclaimer::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 1);
+        let w = round_trip(w);
+        assert_eq!((w.strong_count(), w.weak_count()), (0, 1));
+        assert!(w.upgrade().is_none());
+        drop(w);
+        Reclaimer::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 1);
+    }
 }
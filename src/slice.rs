@@ -0,0 +1,208 @@
+use crate::shared_ptrs::ArcInner;
+use crate::smr::drc::Retire;
+use crate::{AllocError, Arc};
+use alloc::alloc::{handle_alloc_error, Layout};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::NonNull;
+use core::{ptr, slice};
+
+/// An immutable slice that is stored in the same allocation as its [`Arc`].
+///
+/// [`Arc`] requires `T` to be [`Sized`], so it can't hold a `[T]`, and `Arc<Vec<T>>` costs a
+/// second allocation. `ArcSlice` is the header of a slice whose elements follow it in the `Arc`'s
+/// allocation, so an `Arc<ArcSlice<T>>` stores its reference counts, its length, and its elements
+/// together. It derefs to `[T]`, and it can be held by an [`AtomicArc`] or a [`Snapshot`] like
+/// any other value. Like [`std::sync::Arc<[T]>`], the elements are dropped when the value is
+/// reclaimed.
+///
+/// An `ArcSlice` only ever exists in such an allocation, so it can't be moved out of it:
+/// [`Arc::get_mut`] and [`Arc::try_unwrap`] always fail for an `Arc<ArcSlice<T>>`.
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::Ordering::SeqCst;
+/// use aarc::{Arc, ArcSlice, AtomicArc, Snapshot};
+///
+/// let buf: Arc<ArcSlice<u8>> = (0..4).collect();
+/// assert_eq!(buf[2], 2);
+/// assert_eq!(&buf[..], &[0, 1, 2, 3]);
+///
+/// let atomic = AtomicArc::from(&buf);
+/// let snapshot = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+/// assert!(Snapshot::ptr_eq(&snapshot, &buf));
+/// ```
+///
+/// [`AtomicArc`]: `crate::AtomicArc`
+/// [`Snapshot`]: `crate::Snapshot`
+#[repr(C)]
+pub struct ArcSlice<T> {
+    // Read back to free the allocation, so it must come first (see `TRAILING`).
+    layout: Layout,
+    ptr: NonNull<T>,
+    len: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T: 'static> ArcSlice<T> {
+    /// Returns the layout of an allocation holding `len` elements, and the offset of the first,
+    /// or [`None`] if it would be too large.
+    fn layout(len: usize) -> Option<(Layout, usize)> {
+        let (layout, offset) = Layout::new::<ArcInner<Self>>()
+            .extend(Layout::array::<T>(len).ok()?)
+            .ok()?;
+        Some((layout.pad_to_align(), offset))
+    }
+}
+
+impl<T: 'static, R: Retire> Arc<ArcSlice<T>, R> {
+    /// Similar to `Arc::from(vec)`, but returns an error instead of aborting if the allocation
+    /// fails. The elements are dropped in that case.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::{Arc, ArcSlice};
+    ///
+    /// let buf = Arc::<ArcSlice<u8>>::try_from_vec(vec![0; 4096]).expect("out of memory");
+    /// assert_eq!(buf.len(), 4096);
+    /// ```
+    pub fn try_from_vec(vec: Vec<T>) -> Result<Self, AllocError> {
+        let (layout, offset) = ArcSlice::<T>::layout(vec.len()).ok_or(AllocError)?;
+        unsafe {
+            let inner = Self::try_alloc_trailing(layout)?;
+            Ok(Self::init(inner, vec, layout, offset))
+        }
+    }
+    /// Moves the elements of `vec` into `inner`, an allocation with the given layout, and writes
+    /// the header in front of them.
+    unsafe fn init(
+        inner: *mut ArcInner<ArcSlice<T>>,
+        mut vec: Vec<T>,
+        layout: Layout,
+        offset: usize,
+    ) -> Self {
+        let len = vec.len();
+        // The pointer is derived from the allocation rather than from the header, so that it may
+        // be used to access the elements.
+        let ptr = inner.cast::<u8>().add(offset).cast::<T>();
+        // The elements are moved into the new allocation, so the Vec must only free its buffer.
+        ptr::copy_nonoverlapping(vec.as_ptr(), ptr, len);
+        vec.set_len(0);
+        inner.cast::<ArcSlice<T>>().write(ArcSlice {
+            layout,
+            ptr: NonNull::new_unchecked(ptr),
+            len,
+            phantom: PhantomData,
+        });
+        Self::from_raw(inner as *const ArcSlice<T>)
+    }
+}
+
+impl<T: Debug> Debug for ArcSlice<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Deref for ArcSlice<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for ArcSlice<T> {
+    fn drop(&mut self) {
+        // The allocation itself is freed by the `Arc`.
+        unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len)) }
+    }
+}
+
+impl<T: 'static, R: Retire> FromIterator<T> for Arc<ArcSlice<T>, R> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T: 'static, R: Retire> From<Vec<T>> for Arc<ArcSlice<T>, R> {
+    fn from(vec: Vec<T>) -> Self {
+        let (layout, offset) = ArcSlice::<T>::layout(vec.len()).expect("slice is too large");
+        unsafe {
+            let inner =
+                Self::try_alloc_trailing(layout).unwrap_or_else(|_| handle_alloc_error(layout));
+            Self::init(inner, vec, layout, offset)
+        }
+    }
+}
+
+impl<T: 'static + Clone, R: Retire> From<&[T]> for Arc<ArcSlice<T>, R> {
+    fn from(value: &[T]) -> Self {
+        value.iter().cloned().collect()
+    }
+}
+
+unsafe impl<T: Send> Send for ArcSlice<T> {}
+
+unsafe impl<T: Sync> Sync for ArcSlice<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::smr::standard_reclaimer::StandardReclaimer;
+    use crate::utils::test_utils::DropCounter;
+    use crate::{Arc, ArcSlice, AtomicArc, Snapshot};
+    use std::sync::atomic::Ordering::SeqCst;
+
+    #[test]
+    fn test_arc_slice_drops_elements() {
+        struct Marker;
+        let s: Arc<ArcSlice<_>> = (0..5).map(|_| DropCounter::<Marker>::new()).collect();
+        let t = s.clone();
+        let w = Arc::downgrade(&s);
+        assert_eq!(Arc::strong_count(&s), 2);
+        assert_eq!(Arc::weak_count(&s), 1);
+        drop(s);
+        StandardReclaimer::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 0);
+        assert_eq!(t.len(), 5);
+        drop(t);
+        StandardReclaimer::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 5);
+        assert!(w.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_arc_slice_edge_cases() {
+        let empty = Arc::<ArcSlice<String>>::from(Vec::new());
+        assert!(empty.is_empty());
+
+        let zsts = Arc::<ArcSlice<()>>::from(&[(), (), ()][..]);
+        assert_eq!(zsts.len(), 3);
+
+        let aligned = Arc::<ArcSlice<u128>>::from(&[1, 2][..]);
+        assert_eq!(aligned.as_ptr() as usize % std::mem::align_of::<u128>(), 0);
+        assert_eq!(&aligned[..], &[1, 2]);
+    }
+
+    #[test]
+    fn test_arc_slice_try_from_vec() {
+        let s = Arc::<ArcSlice<_>>::try_from_vec(vec![5, 3]).unwrap();
+        assert_eq!(&s[..], &[5, 3]);
+    }
+
+    #[test]
+    fn test_arc_slice_stays_in_place() {
+        let mut s = Arc::<ArcSlice<u8>>::from(&[1, 2, 3][..]);
+        assert!(Arc::get_mut(&mut s).is_none());
+        let s = Arc::try_unwrap(s).unwrap_err();
+
+        let atomic = AtomicArc::from(&s);
+        let snapshot = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+        atomic.store(Some(&Arc::<ArcSlice<_>>::from(&[4, 5][..])), SeqCst);
+        drop(s);
+        assert_eq!(&snapshot[..], &[1, 2, 3]);
+        assert_eq!(&atomic.load::<Snapshot<_>>(SeqCst).unwrap()[..], &[4, 5]);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::smr::drc::{Protect, ProtectPtr, Release, Retire, Retirement};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr::null_mut;
+use std::sync::Mutex;
+
+/// A reclaimer that reclaims each retired object as soon as it is provably safe to do so, for use
+/// in tests.
+///
+/// Retired objects are reclaimed immediately, within the call that retires them, unless a
+/// [`Snapshot`] protects them or some thread is in a critical section. In that case, they are
+/// reclaimed as soon as the last such protection is released or the last critical section ends.
+/// Use-after-free bugs therefore surface at a predictable point (e.g. under Miri), rather than
+/// whenever a batch happens to fill up.
+///
+/// All operations take a global lock, so this reclaimer is only suitable for testing.
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::AtomicBool;
+/// use std::sync::atomic::Ordering::SeqCst;
+/// use aarc::smr::deterministic_reclaimer::DeterministicReclaimer;
+/// use aarc::{Arc, AtomicArc, Snapshot};
+///
+/// static DROPPED: AtomicBool = AtomicBool::new(false);
+/// struct Flagged;
+/// impl Drop for Flagged {
+///     fn drop(&mut self) {
+///         DROPPED.store(true, SeqCst);
+///     }
+/// }
+///
+/// let atomic = AtomicArc::from(&Arc::<_, DeterministicReclaimer>::new_in(Flagged));
+/// let snapshot = atomic.load::<Snapshot<_, DeterministicReclaimer>>(SeqCst).unwrap();
+/// atomic.store::<Arc<_, DeterministicReclaimer>>(None, SeqCst);
+/// assert!(!DROPPED.load(SeqCst));
+///
+/// // Releasing the last protection reclaims the value right away.
+/// drop(snapshot);
+/// assert!(DROPPED.load(SeqCst));
+/// ```
+///
+/// [`Snapshot`]: `crate::Snapshot`
+pub struct DeterministicReclaimer;
+
+struct State {
+    // The number of critical sections, across all threads, that have not ended yet.
+    critical_sections: usize,
+    // The number of protection handles held for each protected pointer.
+    protected: BTreeMap<*mut u8, usize>,
+    // Retirements that were unsafe to reclaim when they were retired.
+    pending: Vec<Retirement>,
+    // Released handles, which any thread may reuse.
+    free_handles: Vec<&'static DeterministicHandle>,
+}
+
+// Safety: the pointers are only compared, retired objects may be reclaimed by any thread, as with
+// the other reclaimers, and a free handle is only accessed by the thread that claims it.
+unsafe impl Send for State {}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    critical_sections: 0,
+    protected: BTreeMap::new(),
+    pending: Vec::new(),
+    free_handles: Vec::new(),
+});
+
+thread_local! {
+    // Set while the current thread is reclaiming, so that objects retired by reclaiming others
+    // are queued instead of recursing once per link of a chain.
+    static RECLAIMING: Cell<bool> = const { Cell::new(false) };
+    static QUEUED: RefCell<Vec<Box<dyn Fn()>>> = const { RefCell::new(Vec::new()) };
+}
+
+impl DeterministicReclaimer {
+    /// Returns the number of retired objects that are waiting for a protection to be released or
+    /// a critical section to end.
+    pub fn pending_retired_count() -> usize {
+        STATE.lock().unwrap().pending.len()
+    }
+    /// Removes the pending retirements that are now safe to reclaim and reclaims them.
+    fn reclaim_pending(state: &mut State) -> Vec<Box<dyn Fn()>> {
+        if state.critical_sections != 0 {
+            return Vec::new();
+        }
+        let (ready, pending) = mem::take(&mut state.pending)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(ptr, _)| !state.protected.contains_key(ptr));
+        state.pending = pending;
+        ready.into_iter().map(|(_, f)| f).collect()
+    }
+    fn run(functions: Vec<Box<dyn Fn()>>) {
+        if functions.is_empty() {
+            return;
+        }
+        QUEUED.with_borrow_mut(|queued| queued.extend(functions));
+        if RECLAIMING.replace(true) {
+            return;
+        }
+        while let Some(f) = QUEUED.with_borrow_mut(Vec::pop) {
+            // Contain panics in the same way as the standard reclaimer.
+            let _ = panic::catch_unwind(AssertUnwindSafe(f));
+        }
+        RECLAIMING.set(false);
+    }
+}
+
+impl Protect for DeterministicReclaimer {
+    fn begin_critical_section() {
+        STATE.lock().unwrap().critical_sections += 1;
+    }
+
+    fn end_critical_section() {
+        let mut state = STATE.lock().unwrap();
+        state.critical_sections -= 1;
+        let ready = Self::reclaim_pending(&mut state);
+        drop(state);
+        Self::run(ready);
+    }
+}
+
+impl ProtectPtr for DeterministicReclaimer {
+    type ProtectionHandle = DeterministicHandle;
+    fn protect_ptr(ptr: *mut u8) -> &'static DeterministicHandle {
+        let mut state = STATE.lock().unwrap();
+        *state.protected.entry(ptr).or_default() += 1;
+        let handle = state
+            .free_handles
+            .pop()
+            .unwrap_or_else(|| Box::leak(Box::default()));
+        handle.ptr.set(ptr);
+        handle
+    }
+    fn is_protected(ptr: *mut u8) -> bool {
+        let state = STATE.lock().unwrap();
+        state.critical_sections != 0 || state.protected.contains_key(&ptr)
+    }
+}
+
+impl Retire for DeterministicReclaimer {
+    fn retire(ptr: *mut u8, f: Box<dyn Fn()>) {
+        let mut state = STATE.lock().unwrap();
+        if state.critical_sections != 0 || state.protected.contains_key(&ptr) {
+            state.pending.push((ptr, f));
+            return;
+        }
+        drop(state);
+        Self::run(vec![f]);
+    }
+}
+
+/// The protection handle of a [`DeterministicReclaimer`], which protects a single pointer.
+pub struct DeterministicHandle {
+    ptr: Cell<*mut u8>,
+}
+
+impl Default for DeterministicHandle {
+    fn default() -> Self {
+        Self {
+            ptr: Cell::new(null_mut()),
+        }
+    }
+}
+
+impl Release for DeterministicHandle {
+    fn release(&self) {
+        let ptr = self.ptr.replace(null_mut());
+        let mut state = STATE.lock().unwrap();
+        let count = state.protected.get_mut(&ptr).unwrap();
+        *count -= 1;
+        if *count == 0 {
+            state.protected.remove(&ptr);
+        }
+        // Handles are only ever created by leaking them in protect_ptr.
+        state.free_handles.push(unsafe { &*(self as *const Self) });
+        let ready = DeterministicReclaimer::reclaim_pending(&mut state);
+        drop(state);
+        DeterministicReclaimer::run(ready);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::smr::deterministic_reclaimer::DeterministicReclaimer;
+    use crate::utils::test_utils::DropCounter;
+    use crate::{Arc, AtomicArc, Snapshot};
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering::SeqCst;
+    use std::sync::Mutex;
+    use std::thread;
+
+    type R = DeterministicReclaimer;
+
+    // Critical sections on any thread defer reclamation, so tests that observe it can't overlap.
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_reclaim_on_release() {
+        struct Marker;
+
+        let _serial = SERIAL.lock().unwrap();
+        let atomic = AtomicArc::from(&Arc::<_, R>::new_in(DropCounter::<Marker>::new()));
+        let s1 = atomic.load::<Snapshot<_, R>>(SeqCst).unwrap();
+        let s2 = s1.clone();
+        atomic.store::<Arc<_, R>>(None, SeqCst);
+        drop(s1);
+        assert_eq!(DropCounter::<Marker>::drops(), 0);
+        drop(s2);
+        assert_eq!(DropCounter::<Marker>::drops(), 1);
+
+        // Without protections, replacing the value reclaims it immediately.
+        atomic.store(
+            Some(&Arc::<_, R>::new_in(DropCounter::<Marker>::new())),
+            SeqCst,
+        );
+        atomic.store::<Arc<_, R>>(None, SeqCst);
+        assert_eq!(DropCounter::<Marker>::drops(), 2);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        struct Node {
+            _next: Option<Arc<Node, R>>,
+        }
+
+        let mut head = None;
+        for _ in 0..10_000 {
+            head = Some(Arc::<_, R>::new_in(Node { _next: head }));
+        }
+        // Reclaiming one node retires the next, which must not recurse.
+        drop(head);
+    }
+
+    #[test]
+    fn test_concurrent_loads() {
+        static STOP: AtomicBool = AtomicBool::new(false);
+        let _serial = SERIAL.lock().unwrap();
+        let atomic = AtomicArc::from(&Arc::<_, R>::new_in(0));
+        thread::scope(|s| {
+            s.spawn(|| {
+                while !STOP.load(SeqCst) {
+                    let snapshot = atomic.load::<Snapshot<_, R>>(SeqCst).unwrap();
+                    assert!(*snapshot < 100);
+                }
+            });
+            for i in 1..100 {
+                atomic.store(Some(&Arc::<_, R>::new_in(i)), SeqCst);
+            }
+            STOP.store(true, SeqCst);
+        });
+    }
+}
@@ -1,17 +1,137 @@
+#[cfg(feature = "std")]
+use crate::smr::standard_reclaimer::StandardReclaimer;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// A retired pointer, paired with the function that reclaims it.
+pub type Retirement = (*mut u8, Box<dyn Fn()>);
+
 pub trait Protect {
     fn begin_critical_section();
     fn end_critical_section();
 }
 
+default_reclaimer! {
+    /// A guard that keeps the current thread in a critical section of the reclaimer `R` until it
+    /// is dropped, like a pinned `crossbeam-epoch` guard.
+    ///
+    /// No object that is retired while the guard is held is reclaimed before it is dropped, so a
+    /// pointer loaded from an atomic within the critical section (e.g. with
+    /// [`AtomicArc::load_raw`]) remains valid for as long as the guard is, without protecting
+    /// each pointer individually. Critical sections nest, and the guard can't be sent to another
+    /// thread. As with a [`Snapshot`], holding a guard for long delays reclamation for all threads.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::smr::drc::CriticalSection;
+    /// use aarc::{Arc, AtomicArc};
+    ///
+    /// let atomic = AtomicArc::new(Some(53));
+    /// let critical_section = CriticalSection::enter();
+    /// let ptr = atomic.load_raw(SeqCst);
+    /// atomic.store(Some(&Arc::new(54)), SeqCst);
+    /// // The replaced value can't be reclaimed until the critical section ends.
+    /// assert_eq!(unsafe { *ptr }, 53);
+    /// drop(critical_section);
+    /// ```
+    ///
+    /// [`AtomicArc::load_raw`]: `crate::AtomicArc::load_raw`
+    /// [`Snapshot`]: `crate::Snapshot`
+    pub struct CriticalSection<R: Protect> {
+        // Critical sections belong to the thread that entered them.
+        phantom: PhantomData<(R, *const ())>,
+    }
+}
+
+#[cfg(feature = "std")]
+impl CriticalSection<StandardReclaimer> {
+    pub fn enter() -> Self {
+        Self::enter_in()
+    }
+}
+
+/// Enters a critical section of the [`StandardReclaimer`], like `crossbeam_epoch::pin`. This is a
+/// shorthand for [`CriticalSection::enter`].
+///
+/// A pointer loaded with [`AtomicArc::load_raw`] after this call is only valid while the returned
+/// [`Pin`] is held: once it is dropped, the object may be reclaimed at any time.
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::Ordering::SeqCst;
+/// use aarc::{Arc, AtomicArc};
+///
+/// let atomic = AtomicArc::new(Some(53));
+/// let guard = aarc::pin();
+/// let ptr = atomic.load_raw(SeqCst);
+/// atomic.store(Some(&Arc::new(54)), SeqCst);
+/// assert_eq!(unsafe { *ptr }, 53);
+/// drop(guard); // `ptr` must not be dereferenced from here on.
+/// ```
+///
+/// [`AtomicArc::load_raw`]: `crate::AtomicArc::load_raw`
+#[cfg(feature = "std")]
+pub fn pin() -> Pin {
+    CriticalSection::enter()
+}
+
+/// The guard returned by [`pin`]: a [`CriticalSection`] of the [`StandardReclaimer`].
+#[cfg(feature = "std")]
+pub type Pin = CriticalSection<StandardReclaimer>;
+
+impl<R: Protect> CriticalSection<R> {
+    pub fn enter_in() -> Self {
+        R::begin_critical_section();
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<R: Protect> Drop for CriticalSection<R> {
+    fn drop(&mut self) {
+        R::end_critical_section();
+    }
+}
+
 pub trait ProtectPtr {
     type ProtectionHandle: 'static + Release;
     fn protect_ptr(ptr: *mut u8) -> &'static Self::ProtectionHandle;
+    /// Like [`protect_ptr`][`ProtectPtr::protect_ptr`], but may return `None` to have the caller
+    /// keep `ptr` alive by incrementing its strong count instead, e.g. to bound the number of
+    /// protections that a thread holds. The default implementation always protects `ptr`.
+    fn try_protect_ptr(ptr: *mut u8) -> Option<&'static Self::ProtectionHandle> {
+        Some(Self::protect_ptr(ptr))
+    }
+    /// Returns `false` only if no thread can currently be accessing `ptr`, whether through a
+    /// protection handle or from within a critical section. False positives are permitted; the
+    /// default implementation always returns `true`.
+    fn is_protected(_ptr: *mut u8) -> bool {
+        true
+    }
 }
 
 pub trait Release {
     fn release(&self);
+    /// Releases several handles at once. The default implementation releases them one by one.
+    fn release_many(handles: &[&Self])
+    where
+        Self: Sized,
+    {
+        for handle in handles {
+            handle.release();
+        }
+    }
 }
 
 pub trait Retire {
     fn retire(ptr: *mut u8, f: Box<dyn Fn()>);
+    /// Retires several pointers at once. The default implementation retires them one by one.
+    fn retire_many(retirements: Vec<Retirement>) {
+        for (ptr, f) in retirements {
+            Self::retire(ptr, f);
+        }
+    }
 }
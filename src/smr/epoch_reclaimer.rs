@@ -0,0 +1,203 @@
+use crate::smr::drc::{Protect, ProtectPtr, Release, Retire};
+use crossbeam_epoch::Guard;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+
+/// A reclaimer backed by the default collector of [`crossbeam_epoch`].
+///
+/// Critical sections and [`Snapshot`]s pin the current thread, and retired objects are deferred
+/// until every thread that was pinned at the time has unpinned. This lets `aarc` share a single
+/// epoch scheme with other data structures built on `crossbeam-epoch`: see
+/// [`EpochReclaimer::with_external_pin`].
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::Ordering::SeqCst;
+/// use aarc::smr::epoch_reclaimer::EpochReclaimer;
+/// use aarc::{Arc, AtomicArc, Snapshot};
+///
+/// let atomic = AtomicArc::from(&Arc::<_, EpochReclaimer>::new_in(53));
+///
+/// let guard = crossbeam_epoch::pin();
+/// let val = EpochReclaimer::with_external_pin(&guard, || {
+///     *atomic.load::<Snapshot<_, EpochReclaimer>>(SeqCst).unwrap()
+/// });
+/// assert_eq!(val, 53);
+/// ```
+///
+/// [`Snapshot`]: `crate::Snapshot`
+pub struct EpochReclaimer;
+
+impl EpochReclaimer {
+    thread_local! {
+        // One entry per critical section, holding its pin unless it began while externally pinned.
+        static CRITICAL_SECTIONS: RefCell<Vec<Option<Guard>>> = const { RefCell::new(Vec::new()) };
+        static EXTERNAL_PINS: Cell<usize> = const { Cell::new(0) };
+        static FREE_HANDLES: RefCell<Vec<&'static EpochHandle>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Runs `f` with `guard` serving as the protection for the critical sections of any
+    /// `AtomicArc` and `AtomicWeak` operations performed inside it, so that they do not pin the
+    /// thread a second time.
+    ///
+    /// Soundness relies on the external pin outliving every load that it protects; this is
+    /// guaranteed because `guard` is borrowed for the duration of `f`. [`Snapshot`]s created inside
+    /// `f` still acquire their own (nested, and therefore cheap) pins, so they may safely outlive
+    /// `guard`.
+    ///
+    /// # Panics
+    /// Panics if `guard` does not belong to the default collector of `crossbeam_epoch` (for
+    /// example, if it was obtained from [`crossbeam_epoch::unprotected`]).
+    ///
+    /// [`Snapshot`]: `crate::Snapshot`
+    pub fn with_external_pin<V, F: FnOnce() -> V>(guard: &Guard, f: F) -> V {
+        assert!(
+            guard.collector() == Some(crossbeam_epoch::default_collector()),
+            "the guard must be pinned by crossbeam_epoch's default collector"
+        );
+        struct ExternalPin;
+        impl Drop for ExternalPin {
+            fn drop(&mut self) {
+                EpochReclaimer::EXTERNAL_PINS.with(|n| n.set(n.get() - 1));
+            }
+        }
+        Self::EXTERNAL_PINS.with(|n| n.set(n.get() + 1));
+        let _external_pin = ExternalPin;
+        f()
+    }
+    fn is_externally_pinned() -> bool {
+        Self::EXTERNAL_PINS.with(Cell::get) != 0
+    }
+}
+
+impl Protect for EpochReclaimer {
+    fn begin_critical_section() {
+        let guard = (!Self::is_externally_pinned()).then(crossbeam_epoch::pin);
+        Self::CRITICAL_SECTIONS.with_borrow_mut(|guards| guards.push(guard));
+    }
+
+    fn end_critical_section() {
+        // The external pin may have begun or ended since this critical section began, so whether
+        // to unpin is determined by the entry rather than by the current external pins.
+        Self::CRITICAL_SECTIONS.with_borrow_mut(Vec::pop);
+    }
+}
+
+impl ProtectPtr for EpochReclaimer {
+    type ProtectionHandle = EpochHandle;
+    fn protect_ptr(_ptr: *mut u8) -> &'static EpochHandle {
+        let handle = Self::FREE_HANDLES
+            .with_borrow_mut(Vec::pop)
+            .unwrap_or_else(|| Box::leak(Box::default()));
+        *handle.guard.borrow_mut() = Some(crossbeam_epoch::pin());
+        handle
+    }
+}
+
+impl Retire for EpochReclaimer {
+    fn retire(_ptr: *mut u8, f: Box<dyn Fn()>) {
+        let guard = crossbeam_epoch::pin();
+        unsafe {
+            // Contain panics in the same way as the standard reclaimer.
+            guard.defer_unchecked(move || {
+                let _ = panic::catch_unwind(AssertUnwindSafe(f));
+            });
+        }
+    }
+}
+
+/// The protection handle of an [`EpochReclaimer`]: a pin that is held for the lifetime of a
+/// [`Snapshot`].
+///
+/// [`Snapshot`]: `crate::Snapshot`
+#[derive(Default)]
+pub struct EpochHandle {
+    guard: RefCell<Option<Guard>>,
+}
+
+impl Release for EpochHandle {
+    fn release(&self) {
+        drop(self.guard.borrow_mut().take());
+        // Handles are only ever created by leaking them in protect_ptr.
+        let handle: &'static Self = unsafe { &*(self as *const Self) };
+        // The free list may already be gone if this runs during thread teardown, in which case
+        // the handle is simply leaked.
+        let _ = EpochReclaimer::FREE_HANDLES.try_with(|free| free.borrow_mut().push(handle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::smr::drc::CriticalSection;
+    use crate::smr::epoch_reclaimer::EpochReclaimer;
+    use crate::utils::test_utils::DropCounter;
+    use crate::{Arc, AtomicArc, Snapshot};
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::SeqCst;
+
+    #[test]
+    fn test_load_store_and_reclaim() {
+        struct Marker;
+
+        let atomic = AtomicArc::from(&Arc::<_, EpochReclaimer>::new_in(
+            DropCounter::<Marker>::new(),
+        ));
+        let snapshot = atomic.load::<Snapshot<_, EpochReclaimer>>(SeqCst).unwrap();
+        atomic.store(None::<&Snapshot<_, EpochReclaimer>>, SeqCst);
+        drop(snapshot);
+        for _ in 0..1000 {
+            if DropCounter::<Marker>::drops() == 1 {
+                break;
+            }
+            crossbeam_epoch::pin().flush();
+        }
+        assert_eq!(DropCounter::<Marker>::drops(), 1);
+    }
+
+    #[test]
+    fn test_with_external_pin() {
+        let atomic = AtomicArc::from(&Arc::<_, EpochReclaimer>::new_in(AtomicUsize::new(0)));
+        let guard = crossbeam_epoch::pin();
+        let arc = EpochReclaimer::with_external_pin(&guard, || {
+            for _ in 0..10 {
+                let snapshot = atomic.load::<Snapshot<_, EpochReclaimer>>(SeqCst).unwrap();
+                snapshot.fetch_add(1, SeqCst);
+            }
+            atomic.load::<Arc<_, EpochReclaimer>>(SeqCst).unwrap()
+        });
+        drop(guard);
+        assert_eq!(arc.load(SeqCst), 10);
+        assert!(!EpochReclaimer::is_externally_pinned());
+    }
+
+    #[test]
+    fn test_critical_section_outlives_external_pin() {
+        let outer = CriticalSection::<EpochReclaimer>::enter_in();
+        let guard = crossbeam_epoch::pin();
+        let inner =
+            EpochReclaimer::with_external_pin(&guard, CriticalSection::<EpochReclaimer>::enter_in);
+        drop(guard);
+        drop(inner);
+        // The outer critical section still pins the thread.
+        assert!(crossbeam_epoch::is_pinned());
+        drop(outer);
+        assert!(!crossbeam_epoch::is_pinned());
+    }
+
+    #[test]
+    fn test_critical_section_ends_within_external_pin() {
+        let critical_section = CriticalSection::<EpochReclaimer>::enter_in();
+        let guard = crossbeam_epoch::pin();
+        EpochReclaimer::with_external_pin(&guard, || drop(critical_section));
+        drop(guard);
+        // The critical section's pin must not be leaked.
+        assert!(!crossbeam_epoch::is_pinned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_external_pin_rejects_unprotected_guard() {
+        let guard = unsafe { crossbeam_epoch::unprotected() };
+        EpochReclaimer::with_external_pin(guard, || ());
+    }
+}
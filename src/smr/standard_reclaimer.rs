@@ -1,21 +1,205 @@
-use crate::smr::drc::{Protect, ProtectPtr, Release, Retire};
+#[cfg(debug_assertions)]
+use crate::shared_ptrs::LIVE_ALLOCATIONS;
+use crate::smr::drc::{Protect, ProtectPtr, Release, Retire, Retirement};
+use crate::utils::sync::{fence, AtomicBool, AtomicPtr, AtomicUsize};
 use crate::utils::unrolled_linked_list::UnrolledLinkedList;
 use crate::utils::unsafe_arc::UnsafeArc;
+use std::any::{Any, TypeId};
 use std::cell::{Cell, RefCell};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::DerefMut;
-use std::ptr::null_mut;
-use std::sync::atomic::Ordering::SeqCst;
-use std::sync::atomic::{AtomicBool, AtomicPtr};
-use std::sync::OnceLock;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr::{null, null_mut, NonNull};
+use std::sync::atomic::Ordering;
+use std::sync::atomic::Ordering::{Relaxed, SeqCst};
+#[cfg(not(loom))]
+use std::sync::Mutex;
 
-const SLOTS_PER_NODE: usize = 32;
+/// Advanced by each call to [`TunableReclaimer::long_lived_snapshots_count`]; every protection is
+/// stamped with the value that was current when it was acquired.
+#[cfg(not(loom))]
+static SNAPSHOTS_EPOCH: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(loom)]
+loom::lazy_static! {
+    static ref SNAPSHOTS_EPOCH: AtomicUsize = AtomicUsize::new(0);
+}
+
+// Statics can't depend on generic parameters, so each instantiation of `TunableReclaimer` has its
+// slots registered here, keyed by their type. The slots are leaked, even under loom: dropping them
+// would run any pending retirements, which may retire further objects.
+#[cfg(not(loom))]
+static INSTANCES: Mutex<Vec<(TypeId, &'static (dyn Any + Send + Sync))>> = Mutex::new(Vec::new());
+
+#[cfg(loom)]
+loom::lazy_static! {
+    static ref INSTANCES: loom::sync::Mutex<Vec<(TypeId, &'static (dyn Any + Send + Sync))>> =
+        Default::default();
+}
+
+// See `TunableReclaimer::set_snapshots_warning`. This is only read when a thread's entries grow,
+// so a lock is cheap enough (and needs no loom counterpart, as it doesn't order anything else).
+static SNAPSHOTS_WARNING: std::sync::Mutex<(usize, fn(usize))> =
+    std::sync::Mutex::new((1024, warn_of_snapshots));
+
+fn warn_of_snapshots(entries: usize) {
+    eprintln!(
+        "warning: a thread has claimed {entries} entries for `Snapshot`s, which slows down \
+         reclamation; avoid holding `Snapshot`s for long, e.g. in data structures"
+    );
+}
+
+#[cfg(not(loom))]
+thread_local! {
+    // Destructor-free, so that the destructors of other thread-locals can still read it. The
+    // lookup that it points to is freed by `SLOTS_GUARD`.
+    static SLOT_LOOKUP: Cell<LookupState> = const { Cell::new(LookupState::Unused) };
+    static SLOTS_GUARD: SlotsGuard = const { SlotsGuard };
+    static LAST_SLOT: Cell<Option<(TypeId, *const ())>> = const { Cell::new(None) };
+    static DEFERRED_FUNCTIONS: RefCell<Option<Functions>> = const { RefCell::new(None) };
+}
+
+#[cfg(loom)]
+loom::thread_local! {
+    static SLOT_LOOKUP: Cell<LookupState> = Cell::new(LookupState::Unused);
+    static SLOTS_GUARD: SlotsGuard = SlotsGuard;
+    static LAST_SLOT: Cell<Option<(TypeId, *const ())>> = Cell::new(None);
+    static DEFERRED_FUNCTIONS: RefCell<Option<Functions>> = RefCell::new(None);
+}
+
+/// The current thread's view of one instantiation of `TunableReclaimer`, identified by `id`. The
+/// pointers are type-erased `AllSlots` and `Slot` references (the latter is null until claimed).
+struct Lookup {
+    id: TypeId,
+    all_slots: *const (),
+    slot: *const (),
+    release_slot: unsafe fn(*const ()),
+}
+
+#[derive(Clone, Copy)]
+enum LookupState {
+    // The thread hasn't used a reclaimer yet.
+    Unused,
+    Active(NonNull<RefCell<Vec<Lookup>>>),
+    // The thread is exiting and has released its slots.
+    Released,
+}
+
+/// Runs `f` with the current thread's lookup, or returns `None` if the thread has released its
+/// slots because it is exiting.
+fn with_lookup<V, F: FnOnce(&RefCell<Vec<Lookup>>) -> V>(f: F) -> Option<V> {
+    let state = SLOT_LOOKUP
+        .try_with(Cell::get)
+        .unwrap_or(LookupState::Released);
+    let lookup = match state {
+        LookupState::Active(lookup) => lookup,
+        LookupState::Released => return None,
+        LookupState::Unused => {
+            // This fails if the thread's thread-locals are already being destroyed.
+            SLOTS_GUARD.try_with(|_| ()).ok()?;
+            let lookup = NonNull::from(Box::leak(Box::default()));
+            SLOT_LOOKUP.with(|state| state.set(LookupState::Active(lookup)));
+            lookup
+        }
+    };
+    // Safety: the lookup is only freed by `SlotsGuard`, which marks it as released first.
+    Some(f(unsafe { lookup.as_ref() }))
+}
+
+/// Returns an id for the current thread that is unique among running threads.
+fn thread_id() -> usize {
+    SLOT_LOOKUP.with(|state| (state as *const Cell<LookupState>).addr())
+}
+
+/// Releases the current thread's slots when it exits.
+struct SlotsGuard;
+
+impl Drop for SlotsGuard {
+    fn drop(&mut self) {
+        // The slots (and any retirements still in their batches) can now be taken over by other
+        // threads. The destructors of other thread-locals may still use a reclaimer afterwards,
+        // in which case they claim a slot for the duration of that use (see
+        // `TunableReclaimer::get_or_claim_exiting_slot`).
+        let state = SLOT_LOOKUP.try_with(|state| state.replace(LookupState::Released));
+        if let Ok(LookupState::Active(lookup)) = state {
+            let _ = LAST_SLOT.try_with(|last| last.set(None));
+            let lookup = unsafe { Box::from_raw(lookup.as_ptr()) };
+            for l in lookup.into_inner() {
+                if !l.slot.is_null() {
+                    unsafe { (l.release_slot)(l.slot) };
+                }
+            }
+        }
+    }
+}
+
+type AllSlots<const SLOTS: usize, const SNAPS: usize> = UnrolledLinkedList<Slot<SNAPS>, SLOTS>;
 
 /// The default memory reclamation strategy.
-pub struct StandardReclaimer;
+///
+/// Retired objects are reclaimed in batches. If a retired destructor panics, the panic is caught
+/// and the rest of the batch is still reclaimed.
+pub type StandardReclaimer = TunableReclaimer<32, 8>;
+
+/// [`StandardReclaimer`] with configurable fan-out and batch size.
+///
+/// Threads claim slots from a list with `SLOTS_PER_NODE` slots per node, and each slot holds its
+/// thread's [`Snapshot`] protections in a list with `SNAPSHOT_PTRS_PER_NODE` entries per node.
+/// On machines with many cores (or threads that hold many `Snapshot`s), larger nodes shorten the
+/// linked lists that are traversed when claiming slots and dispatching batches.
+///
+/// Each thread collects the objects it retires into a batch, which is dispatched for reclamation
+/// once it holds `BATCH_SIZE` objects. Small batches reclaim memory sooner, while large batches
+/// amortize the cost of checking every thread's `Snapshot`s. If `BATCH_SIZE` is 0 (the default),
+/// batches hold as many objects as there are slots, so they grow with the number of threads.
+///
+/// Each instantiation is an independent reclaimer, so objects may only be shared between
+/// pointers that use the same parameters. `Domain` only serves to tell instantiations apart: a
+/// library can use a marker type of its own to keep its retired objects from waiting on the
+/// `Snapshot`s and critical sections of the rest of the program, and vice versa.
+///
+/// # Examples
+/// ```
+/// use aarc::smr::standard_reclaimer::TunableReclaimer;
+/// use aarc::Arc;
+///
+/// type WideReclaimer = TunableReclaimer<128, 16>;
+///
+/// let x = Arc::<_, WideReclaimer>::new_in(53);
+/// assert_eq!(*x, 53);
+///
+/// // Reclaims in batches of 4, regardless of the number of threads.
+/// type EagerReclaimer = TunableReclaimer<32, 8, 4>;
+///
+/// let y = Arc::<_, EagerReclaimer>::new_in(53);
+/// assert_eq!(*y, 53);
+/// ```
+///
+/// [`Snapshot`]: `crate::Snapshot`
+pub struct TunableReclaimer<
+    const SLOTS_PER_NODE: usize,
+    const SNAPSHOT_PTRS_PER_NODE: usize,
+    const BATCH_SIZE: usize = 0,
+    Domain = (),
+>(PhantomData<fn() -> Domain>);
 
-impl StandardReclaimer {
+/// A reclaimer that no other test shares, for tests that observe reclamation: a test's objects
+/// could otherwise wait on the critical sections and `Snapshot`s of tests running concurrently.
+/// Each marker type `M` (typically a struct declared in the test) is a separate reclaimer.
+#[cfg(test)]
+pub(crate) type IsolatedReclaimer<
+    M,
+    const SLOTS: usize = 32,
+    const SNAPS: usize = 8,
+    const BATCH: usize = 0,
+> = TunableReclaimer<SLOTS, SNAPS, BATCH, M>;
+
+impl<const SLOTS: usize, const SNAPS: usize, const BATCH: usize, Domain: 'static>
+    TunableReclaimer<SLOTS, SNAPS, BATCH, Domain>
+{
     /// # Safety
     /// TODO: write docs for this and make it pub
     #[allow(dead_code)]
@@ -28,77 +212,505 @@ impl StandardReclaimer {
             }
         }
     }
-    fn get_all_slots() -> &'static UnrolledLinkedList<Slot, SLOTS_PER_NODE> {
-        static SLOTS: OnceLock<UnrolledLinkedList<Slot, SLOTS_PER_NODE>> = OnceLock::new();
-        SLOTS.get_or_init(UnrolledLinkedList::default)
+    /// Returns the number of [`Snapshot`]s, across all threads, that have been held since before
+    /// the previous call to this function.
+    ///
+    /// A long-lived `Snapshot` delays the reclamation of every retired object that it conflicts
+    /// with. Calling this function periodically (e.g. from a monitoring thread) helps to diagnose
+    /// memory that is not being freed: a nonzero result means that some `Snapshot`s have outlived
+    /// at least one full interval between calls. It does not affect the `Snapshot`s themselves.
+    ///
+    /// [`Snapshot`]: `crate::Snapshot`
+    pub fn long_lived_snapshots_count() -> usize {
+        let epoch = SNAPSHOTS_EPOCH.fetch_add(1, SeqCst);
+        Self::get_all_slots()
+            .iter(SeqCst)
+            .flat_map(|slot| slot.snapshots.iter(SeqCst))
+            .filter(|s| !s.ptr.load(SeqCst).is_null() && s.epoch.load(Relaxed) < epoch)
+            .count()
     }
-    thread_local! {
-        static SLOT_LOOKUP: Cell<Option<&'static Slot>> = Default::default();
+    /// Returns the number of protections that the current thread holds for its [`Snapshot`]s.
+    ///
+    /// `Snapshot`s of the same pointer share a protection, so this can be smaller than the number
+    /// of `Snapshot`s; those that hold a strong count instead (see
+    /// [`set_snapshots_limit`][`Self::set_snapshots_limit`]) are not counted. Code that holds
+    /// many `Snapshot`s can use this to decide when to convert some of them to [`Arc`]s.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::smr::standard_reclaimer::StandardReclaimer;
+    /// use aarc::{AtomicArc, Snapshot};
+    ///
+    /// let atomics: Vec<_> = (0..3).map(|i| AtomicArc::new(Some(i))).collect();
+    /// let before = StandardReclaimer::local_snapshots_count();
+    /// let snapshots: Vec<Snapshot<_>> = atomics.iter().map(|a| a.load(SeqCst).unwrap()).collect();
+    /// let clone = snapshots[0].clone();
+    /// assert_eq!(StandardReclaimer::local_snapshots_count(), before + 3);
+    /// ```
+    ///
+    /// [`Arc`]: `crate::Arc`
+    /// [`Snapshot`]: `crate::Snapshot`
+    pub fn local_snapshots_count() -> usize {
+        Self::with_slot(|slot| {
+            slot.snapshots
+                .iter(SeqCst)
+                .filter(|s| !s.ptr.load(Relaxed).is_null())
+                .count()
+        })
     }
-    fn get_or_claim_slot() -> &'static Slot {
-        Self::SLOT_LOOKUP.with(|lookup| {
-            if let Some(slot) = lookup.get() {
-                slot
-            } else {
-                let claimed = Self::get_all_slots().try_for_each_with_append(|slot| {
-                    slot.is_claimed
-                        .compare_exchange(false, true, SeqCst, SeqCst)
-                        .is_ok()
+    /// Returns the number of objects that have been retired, across all threads, but not yet
+    /// reclaimed.
+    ///
+    /// This includes objects in batches that haven't filled up yet (see [`flush`]) as well as
+    /// those that are waiting for a [`Snapshot`] to be dropped or for a thread to leave a critical
+    /// section. If it keeps growing while [`long_lived_snapshots_count`] is nonzero, a stuck
+    /// reader is likely preventing reclamation.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::smr::standard_reclaimer::TunableReclaimer;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// type Reclaimer = TunableReclaimer<4, 4>;
+    ///
+    /// let atomic = AtomicArc::from(&Arc::<_, Reclaimer>::new_in(1));
+    /// let snapshot = atomic.load::<Snapshot<_, Reclaimer>>(SeqCst).unwrap();
+    /// atomic.store::<Arc<_, Reclaimer>>(None, SeqCst);
+    /// Reclaimer::flush();
+    /// assert_eq!(Reclaimer::pending_retired_count(), 1);
+    ///
+    /// // Dropping the value releases its allocation, which is retired in turn.
+    /// drop(snapshot);
+    /// Reclaimer::flush();
+    /// assert_eq!(Reclaimer::pending_retired_count(), 0);
+    /// ```
+    ///
+    /// [`flush`]: `TunableReclaimer::flush`
+    /// [`long_lived_snapshots_count`]: `TunableReclaimer::long_lived_snapshots_count`
+    /// [`Snapshot`]: `crate::Snapshot`
+    pub fn pending_retired_count() -> usize {
+        Self::get_all_slots()
+            .iter(SeqCst)
+            .map(|slot| slot.retired_count.load(SeqCst))
+            .sum()
+    }
+    /// Returns the number of slots that have been claimed, i.e. the number of live threads that
+    /// have used this reclaimer. A thread releases its slot when it exits.
+    pub fn slots_in_use() -> usize {
+        Self::get_all_slots()
+            .iter(SeqCst)
+            .filter(|slot| slot.is_claimed.load(SeqCst))
+            .count()
+    }
+    fn get_all_slots() -> &'static AllSlots<SLOTS, SNAPS> {
+        let all_slots =
+            with_lookup(|lookup| Self::find(&lookup.borrow()).map(|l| l.all_slots)).flatten();
+        match all_slots {
+            // Safety: the entry's id guarantees that the pointer has this type.
+            Some(all_slots) => unsafe { &*all_slots.cast() },
+            None => {
+                let all_slots = Self::register();
+                with_lookup(|lookup| {
+                    lookup.borrow_mut().push(Lookup {
+                        id: TypeId::of::<Self>(),
+                        all_slots: (all_slots as *const AllSlots<SLOTS, SNAPS>).cast(),
+                        slot: null(),
+                        release_slot: Self::release_slot,
+                    })
                 });
-                lookup.set(Some(claimed));
-                claimed
+                all_slots
             }
-        })
+        }
     }
-}
-
-impl Protect for StandardReclaimer {
-    fn begin_critical_section() {
-        Self::get_or_claim_slot()
-            .is_in_critical_section
-            .store(true, SeqCst);
+    fn find(lookup: &[Lookup]) -> Option<&Lookup> {
+        lookup.iter().find(|l| l.id == TypeId::of::<Self>())
     }
-
-    fn end_critical_section() {
+    /// Finds or creates the slots for this instantiation. This takes a lock, but it only happens
+    /// the first time that each thread uses the instantiation.
+    fn register() -> &'static AllSlots<SLOTS, SNAPS> {
+        let mut instances = INSTANCES.lock().unwrap();
+        // Instantiations that differ only in `BATCH_SIZE` or `Domain` have slots of the same type,
+        // so they are told apart by the type of the reclaimer.
+        if let Some(&(_, all_slots)) = instances.iter().find(|(id, _)| *id == TypeId::of::<Self>())
+        {
+            return all_slots.downcast_ref().unwrap();
+        }
+        let all_slots: &'static AllSlots<SLOTS, SNAPS> = Box::leak(Box::default());
+        instances.push((TypeId::of::<Self>(), all_slots));
+        all_slots
+    }
+    fn get_or_claim_slot() -> &'static Slot<SNAPS> {
+        // Programs rarely use more than one instantiation, so the most recently used slot is
+        // cached separately to keep the common path cheap.
+        if let Some((id, slot)) = LAST_SLOT.with(Cell::get) {
+            if id == TypeId::of::<Self>() {
+                // Safety: the id guarantees that the pointer has this type.
+                return unsafe { &*slot.cast() };
+            }
+        }
+        let Some(slot) = with_lookup(|lookup| Self::find(&lookup.borrow()).map(|l| l.slot)) else {
+            return Self::get_or_claim_exiting_slot();
+        };
+        if let Some(slot) = slot.filter(|slot| !slot.is_null()) {
+            LAST_SLOT.with(|last| last.set(Some((TypeId::of::<Self>(), slot))));
+            // Safety: the entry's id guarantees that the pointer has this type.
+            return unsafe { &*slot.cast() };
+        }
+        let claimed = Self::claim_slot();
+        with_lookup(|lookup| {
+            let mut lookup = lookup.borrow_mut();
+            let entry = lookup
+                .iter_mut()
+                .find(|l| l.id == TypeId::of::<Self>())
+                .unwrap();
+            entry.slot = (claimed as *const Slot<SNAPS>).cast();
+        });
+        LAST_SLOT.with(|last| last.set(Some((TypeId::of::<Self>(), claimed as *const _ as _))));
+        claimed
+    }
+    fn claim_slot() -> &'static Slot<SNAPS> {
+        let claimed = Self::get_all_slots().try_for_each_with_append(|slot| {
+            slot.is_claimed
+                .compare_exchange(false, true, SeqCst, SeqCst)
+                .is_ok()
+        });
+        // The previous owner may still hold snapshots, e.g. in thread-locals that are destroyed
+        // after it released the slot, so their entries must not be shared.
+        claimed.snapshots_by_addr.borrow_mut().clear();
+        claimed
+    }
+    /// Returns the slot that the current thread uses after having released its slots on exit,
+    /// claiming one if necessary. Without its lookup, the thread finds the slot by its id. The
+    /// slot is released by [`release_if_exiting`] as soon as it is idle, and any retirements left
+    /// in its batch are dispatched like those of any other exited thread.
+    ///
+    /// [`release_if_exiting`]: `TunableReclaimer::release_if_exiting`
+    fn get_or_claim_exiting_slot() -> &'static Slot<SNAPS> {
+        let id = thread_id();
+        let all_slots = Self::get_all_slots();
+        if let Some(slot) = all_slots
+            .iter(SeqCst)
+            .find(|slot| slot.exiting_owner.load(SeqCst) == id)
+        {
+            return slot;
+        }
+        let claimed = Self::claim_slot();
+        claimed.exiting_owner.store(id, SeqCst);
+        claimed
+    }
+    /// Runs `f` with the current thread's slot, releasing it afterwards if the thread has already
+    /// released its slots on exit.
+    fn with_slot<V, F: FnOnce(&'static Slot<SNAPS>) -> V>(f: F) -> V {
         let slot = Self::get_or_claim_slot();
-        slot.is_in_critical_section.store(false, SeqCst);
-        slot.primary_list.detach_head();
+        let v = f(slot);
+        Self::release_if_exiting(slot);
+        v
     }
-}
-
-impl ProtectPtr for StandardReclaimer {
-    type ProtectionHandle = SnapshotPtr;
-    fn protect_ptr(ptr: *mut u8) -> &'static SnapshotPtr {
-        // TODO: don't search from the beginning every time
-        Self::get_or_claim_slot()
-            .snapshots
-            .try_for_each_with_append(|s| {
-                s.ptr
-                    .compare_exchange(null_mut(), ptr, SeqCst, SeqCst)
-                    .is_ok()
-            })
+    /// Releases `slot` if the current thread claimed it after having released its slots on exit
+    /// and is not in a critical section.
+    fn release_if_exiting(slot: &'static Slot<SNAPS>) {
+        if slot.critical_section_depth.get() == 0 && slot.exiting_owner.load(SeqCst) == thread_id()
+        {
+            slot.exiting_owner.store(0, SeqCst);
+            unsafe { Self::release_slot((slot as *const Slot<SNAPS>).cast()) };
+        }
     }
-}
-
-impl Retire for StandardReclaimer {
-    fn retire(ptr: *mut u8, f: Box<dyn Fn()>) {
-        let mut borrowed = Self::get_or_claim_slot().batch.borrow_mut();
-        borrowed.functions.push(f);
-        borrowed.ptrs.insert(ptr);
-        if borrowed.functions.len() < borrowed.functions.capacity() {
+    /// Releases a slot claimed by a thread that is exiting. Its batch is left in place, to be
+    /// dispatched by [`dispatch_orphaned_batches`] or taken over by the next thread to claim it.
+    ///
+    /// [`dispatch_orphaned_batches`]: `TunableReclaimer::dispatch_orphaned_batches`
+    unsafe fn release_slot(slot: *const ()) {
+        let slot = &*slot.cast::<Slot<SNAPS>>();
+        slot.snapshots_limit.set(None);
+        slot.is_claimed.store(false, SeqCst);
+    }
+    /// Dispatches the batches left in the slots of threads that have exited, which would
+    /// otherwise wait until another thread claims those slots.
+    fn dispatch_orphaned_batches() {
+        for slot in Self::get_all_slots().iter(SeqCst) {
+            // Claiming the slot gives this thread exclusive access to its batch.
+            if slot
+                .is_claimed
+                .compare_exchange(false, true, SeqCst, SeqCst)
+                .is_err()
+            {
+                continue;
+            }
+            let batch = slot.batch.take();
+            slot.is_claimed.store(false, SeqCst);
+            if !batch.functions.is_empty() {
+                Self::dispatch(batch);
+            }
+        }
+    }
+    /// Claims a slot for the current thread now, rather than on its first use of this reclaimer.
+    ///
+    /// Claiming a slot searches the list of slots for a free one, and may allocate a new node. A
+    /// thread pool can call this when each thread starts, so that the first request served by a
+    /// thread doesn't pay this cost. It has no effect if the thread already has a slot.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::thread;
+    /// use aarc::smr::standard_reclaimer::StandardReclaimer;
+    ///
+    /// let worker = thread::spawn(|| {
+    ///     StandardReclaimer::prewarm();
+    ///     // Serve requests...
+    /// });
+    /// worker.join().unwrap();
+    /// ```
+    pub fn prewarm() {
+        Self::with_slot(|_| ());
+    }
+    /// Submits the objects that the current thread has retired for reclamation now, rather than
+    /// waiting for its batch to fill up. This is the same processing that happens when a batch
+    /// is full, so objects that are still protected by a [`Snapshot`] (or that another thread
+    /// may be reading in a critical section) are still reclaimed later.
+    ///
+    /// Calling this at a convenient point, e.g. between requests, bounds when the cost of
+    /// reclamation is paid. See also [`ReclaimScope`].
+    ///
+    /// This also submits the objects retired by threads that have since exited, which are
+    /// otherwise only submitted when a thread's batch fills up.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::smr::standard_reclaimer::StandardReclaimer;
+    /// use aarc::{Arc, AtomicArc};
+    ///
+    /// let atomic = AtomicArc::new(Some(0));
+    /// for request in 1..=3 {
+    ///     atomic.store(Some(&Arc::new(request)), SeqCst);
+    ///     StandardReclaimer::flush();
+    /// }
+    /// ```
+    ///
+    /// [`Snapshot`]: `crate::Snapshot`
+    pub fn flush() {
+        Self::with_slot(|slot| {
+            Self::dispatch_orphaned_batches();
+            let mut borrowed = slot.batch.borrow_mut();
+            if borrowed.functions.is_empty() {
+                return;
+            }
+            let capacity = borrowed.functions.capacity();
+            let batch = mem::replace(
+                borrowed.deref_mut(),
+                Batch {
+                    functions: Vec::with_capacity(capacity),
+                    ptrs: HashSet::with_capacity(capacity),
+                    retired_count: None,
+                },
+            );
+            drop(borrowed);
+            Self::dispatch(batch);
+        });
+    }
+    /// Limits the number of [`Snapshot`] protections that the current thread holds, or removes the
+    /// limit if `limit` is [`None`]. The limit is rounded up to a whole number of nodes of
+    /// `SNAPSHOT_PTRS_PER_NODE` entries.
+    ///
+    /// Each protection that a thread holds makes reclamation slightly slower, so holding too many
+    /// `Snapshot`s degrades performance. Past the limit, new `Snapshot`s (other than those of a
+    /// pointer that is already protected) increment the strong count instead, like an [`Arc`].
+    /// This trades some contention on the counts for a bounded number of protections, which suits
+    /// traversals that legitimately hold many references at once.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::smr::standard_reclaimer::StandardReclaimer;
+    /// use aarc::{Arc, AtomicArc, Snapshot};
+    ///
+    /// StandardReclaimer::set_snapshots_limit(Some(8));
+    /// let arcs: Vec<_> = (0..9).map(Arc::new).collect();
+    /// let atomics: Vec<_> = arcs.iter().map(AtomicArc::from).collect();
+    /// let snapshots: Vec<Snapshot<_>> = atomics.iter().map(|a| a.load(SeqCst).unwrap()).collect();
+    ///
+    /// // Only the ninth `Snapshot` holds a strong count.
+    /// assert_eq!(Arc::strong_count(&arcs[7]), 2);
+    /// assert_eq!(Arc::strong_count(&arcs[8]), 3);
+    /// ```
+    ///
+    /// [`Arc`]: `crate::Arc`
+    /// [`Snapshot`]: `crate::Snapshot`
+    pub fn set_snapshots_limit(limit: Option<usize>) {
+        Self::with_slot(|slot| slot.snapshots_limit.set(limit));
+    }
+    /// Sets the number of [`Snapshot`] entries past which a thread is considered to be holding too
+    /// many, and the function that is called with the thread's new number of entries when they
+    /// grow past it. This is only checked in debug builds.
+    ///
+    /// A thread's entries only grow when it holds more `Snapshot`s at once than it has entries, so
+    /// crossing the threshold usually means that `Snapshot`s are being kept around (e.g. in a data
+    /// structure) where an [`Arc`] would be more appropriate. Since each entry makes reclamation
+    /// slightly slower, this otherwise shows up only as a gradual slowdown. By default, the
+    /// threshold is 1024 entries and a warning is printed to stderr. The setting is shared by all
+    /// `TunableReclaimer`s.
+    ///
+    /// # Examples
+    /// ```
+    /// use aarc::smr::standard_reclaimer::StandardReclaimer;
+    ///
+    /// StandardReclaimer::set_snapshots_warning(4096, |entries| {
+    ///     panic!("a thread is holding {entries} snapshots");
+    /// });
+    /// ```
+    ///
+    /// [`Arc`]: `crate::Arc`
+    /// [`Snapshot`]: `crate::Snapshot`
+    pub fn set_snapshots_warning(threshold: usize, callback: fn(usize)) {
+        *SNAPSHOTS_WARNING.lock().unwrap() = (threshold, callback);
+    }
+    /// Calls the callback of [`set_snapshots_warning`][`Self::set_snapshots_warning`] if the
+    /// current thread's entries grew from `before` to `after` nodes past the threshold.
+    fn check_snapshots_warning(before: usize, after: usize) {
+        if after > before {
+            let (threshold, callback) = *SNAPSHOTS_WARNING.lock().unwrap();
+            if before * SNAPS <= threshold && after * SNAPS > threshold {
+                callback(after * SNAPS);
+            }
+        }
+    }
+    /// Protects `ptr` with one of the current thread's entries. If every entry is in use and the
+    /// thread already has at least `limit` entries, returns `None` instead of adding more.
+    fn protect_ptr_within(ptr: *mut u8, limit: Option<usize>) -> Option<&'static SnapshotPtr> {
+        Self::with_slot(|slot| Self::protect_ptr_in_slot(slot, ptr, limit))
+    }
+    fn protect_ptr_in_slot(
+        slot: &'static Slot<SNAPS>,
+        ptr: *mut u8,
+        limit: Option<usize>,
+    ) -> Option<&'static SnapshotPtr> {
+        let epoch = SNAPSHOTS_EPOCH.load(Relaxed);
+        let mut by_addr = slot.snapshots_by_addr.borrow_mut();
+        if let Some(&snapshot_ptr) = by_addr.get(&ptr) {
+            // The entry may have been released (and reused for another pointer) since then. Only
+            // the owning thread writes to it, so a matching pointer means it is still protected.
+            if snapshot_ptr.ptr.load(Relaxed) == ptr {
+                snapshot_ptr.count.fetch_add(1, Relaxed);
+                return Some(snapshot_ptr);
+            }
+        }
+        let claim = |s: &SnapshotPtr| {
+            // Only the owning thread claims entries, so the stamp can't be overwritten while the
+            // entry is in use.
+            if !s.ptr.load(Relaxed).is_null() {
+                return false;
+            }
+            s.epoch.store(epoch, Relaxed);
+            s.count.store(1, Relaxed);
+            s.ptr
+                .compare_exchange(null_mut(), ptr, SeqCst, SeqCst)
+                .is_ok()
+        };
+        let (index, snapshot_ptr) =
+            if limit.is_some_and(|limit| slot.snapshots.get_nodes_count() * SNAPS >= limit) {
+                slot.snapshots
+                    .iter(SeqCst)
+                    .enumerate()
+                    .find(|(_, s)| claim(s))?
+            } else {
+                // Resume after the most recently claimed entry rather than searching from the
+                // beginning, so that holding many snapshots doesn't make each acquisition linear.
+                let start = slot.next_snapshot_index.get();
+                let nodes_count = slot.snapshots.get_nodes_count();
+                let claimed = slot.snapshots.try_for_each_with_append_from(start, claim);
+                if cfg!(debug_assertions) {
+                    Self::check_snapshots_warning(nodes_count, slot.snapshots.get_nodes_count());
+                }
+                claimed
+            };
+        slot.next_snapshot_index.set(index + 1);
+        // Drop the entries of released pointers once they outnumber the claimable entries.
+        if by_addr.len() >= slot.snapshots.get_nodes_count() * SNAPS {
+            by_addr.retain(|&p, s| s.ptr.load(Relaxed) == p);
+        }
+        by_addr.insert(ptr, snapshot_ptr);
+        Some(snapshot_ptr)
+    }
+    /// Reclaims everything that the current thread has retired, then panics if any [`Arc`]
+    /// allocation (under any reclaimer) has not been freed. Only available in debug builds.
+    ///
+    /// This is meant to be called at the end of a test, once all other threads have exited and
+    /// no [`Snapshot`]s are held, to turn a reference-count leak into an immediate failure. Any
+    /// objects that other threads retired but have not [`flush`][`Self::flush`]ed are reported
+    /// as leaks, as are any `Arc`s that are still alive, so this is unreliable while other tests
+    /// run concurrently in the same process.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::smr::standard_reclaimer::StandardReclaimer;
+    /// use aarc::{Arc, AtomicArc};
+    ///
+    /// let atomic = AtomicArc::new(Some(0));
+    /// atomic.store(Some(&Arc::new(1)), SeqCst);
+    /// drop(atomic);
+    /// StandardReclaimer::assert_no_leaks();
+    /// ```
+    ///
+    /// An `Arc` that is never dropped is detected:
+    /// ```should_panic
+    /// use aarc::smr::standard_reclaimer::StandardReclaimer;
+    /// use aarc::Arc;
+    ///
+    /// std::mem::forget(Arc::new(53));
+    /// StandardReclaimer::assert_no_leaks();
+    /// ```
+    ///
+    /// [`Arc`]: `crate::Arc`
+    /// [`Snapshot`]: `crate::Snapshot`
+    #[cfg(debug_assertions)]
+    pub fn assert_no_leaks() {
+        // Reclaiming an object may retire others, so flush until nothing is left.
+        while !Self::get_or_claim_slot()
+            .batch
+            .borrow()
+            .functions
+            .is_empty()
+        {
+            Self::flush();
+        }
+        let live = LIVE_ALLOCATIONS.load(SeqCst);
+        assert_eq!(live, 0, "{live} allocations were never freed");
+    }
+    fn add_to_batch<F: FnOnce(&mut Batch)>(add: F) {
+        Self::with_slot(|slot| Self::add_to_slot_batch(slot, add));
+    }
+    fn add_to_slot_batch<F: FnOnce(&mut Batch)>(slot: &'static Slot<SNAPS>, add: F) {
+        let mut borrowed = slot.batch.borrow_mut();
+        let (len, capacity) = (borrowed.functions.len(), borrowed.functions.capacity());
+        add(&mut borrowed);
+        slot.retired_count
+            .fetch_add(borrowed.functions.len() - len, SeqCst);
+        borrowed.retired_count = Some(&slot.retired_count);
+        if borrowed.functions.len() < capacity {
             return;
         }
-        let all_slots = Self::get_all_slots();
-        let next_batch_size = all_slots.get_nodes_count() * SLOTS_PER_NODE;
+        let next_batch_size = match BATCH {
+            0 => Self::get_all_slots().get_nodes_count() * SLOTS,
+            size => size,
+        };
         let batch = mem::replace(
             borrowed.deref_mut(),
             Batch {
                 functions: Vec::with_capacity(next_batch_size),
                 ptrs: HashSet::with_capacity(next_batch_size),
+                retired_count: None,
             },
         );
-        // Drop the borrow before proceeding in case there is a recursive call to this function.
+        // Drop the borrow before proceeding in case there is a recursive call to retire.
         drop(borrowed);
+        Self::dispatch(batch);
+        Self::dispatch_orphaned_batches();
+    }
+    fn dispatch(batch: Batch) {
+        let all_slots = Self::get_all_slots();
         let batch_arc = UnsafeArc::new(batch, 1);
         for slot in all_slots.iter(SeqCst) {
             if slot.is_in_critical_section.load(SeqCst) {
@@ -107,43 +719,230 @@ impl Retire for StandardReclaimer {
                 slot.primary_list.insert(batch_arc.clone(), Some(slot));
             } else {
                 // Otherwise, the snapshots must be checked immediately.
-                for snapshot_ptr in slot.snapshots.iter(SeqCst) {
-                    let p = snapshot_ptr.ptr.load(SeqCst);
-                    if !p.is_null() && batch_arc.ptrs.contains(&p) {
-                        snapshot_ptr.conflicts.insert(batch_arc.clone(), None);
-                    }
-                }
+                slot.record_conflicts(&batch_arc);
             }
         }
     }
 }
 
-const SNAPSHOT_PTRS_PER_NODE: usize = 8;
+impl<const SLOTS: usize, const SNAPS: usize, const BATCH: usize, Domain: 'static> Protect
+    for TunableReclaimer<SLOTS, SNAPS, BATCH, Domain>
+{
+    fn begin_critical_section() {
+        let slot = Self::get_or_claim_slot();
+        let depth = slot.critical_section_depth.get();
+        if depth == 0 {
+            slot.is_in_critical_section.store(true, SeqCst);
+        }
+        slot.critical_section_depth.set(depth + 1);
+    }
+
+    fn end_critical_section() {
+        let slot = Self::get_or_claim_slot();
+        let depth = slot.critical_section_depth.get() - 1;
+        slot.critical_section_depth.set(depth);
+        // Only the outermost critical section ends protection.
+        if depth == 0 {
+            slot.is_in_critical_section.store(false, SeqCst);
+            slot.primary_list.detach_head();
+        }
+        Self::release_if_exiting(slot);
+    }
+}
+
+impl<const SLOTS: usize, const SNAPS: usize, const BATCH: usize, Domain: 'static> ProtectPtr
+    for TunableReclaimer<SLOTS, SNAPS, BATCH, Domain>
+{
+    type ProtectionHandle = SnapshotPtr;
+    fn protect_ptr(ptr: *mut u8) -> &'static SnapshotPtr {
+        Self::protect_ptr_within(ptr, None).unwrap()
+    }
+
+    fn try_protect_ptr(ptr: *mut u8) -> Option<&'static SnapshotPtr> {
+        let limit = Self::get_or_claim_slot().snapshots_limit.get();
+        Self::protect_ptr_within(ptr, limit)
+    }
+
+    fn is_protected(ptr: *mut u8) -> bool {
+        // Any thread in a critical section may be about to protect or resurrect the pointer.
+        Self::get_all_slots().iter(SeqCst).any(|slot| {
+            slot.is_in_critical_section.load(SeqCst)
+                || slot
+                    .snapshots
+                    .iter(SeqCst)
+                    .any(|s| s.ptr.load(SeqCst) == ptr)
+        })
+    }
+}
+
+impl<const SLOTS: usize, const SNAPS: usize, const BATCH: usize, Domain: 'static> Retire
+    for TunableReclaimer<SLOTS, SNAPS, BATCH, Domain>
+{
+    fn retire(ptr: *mut u8, f: Box<dyn Fn()>) {
+        Self::add_to_batch(|batch| {
+            batch.functions.push(f);
+            batch.ptrs.insert(ptr);
+        });
+    }
+
+    fn retire_many(retirements: Vec<Retirement>) {
+        Self::add_to_batch(|batch| {
+            for (ptr, f) in retirements {
+                batch.functions.push(f);
+                batch.ptrs.insert(ptr);
+            }
+        });
+    }
+}
+
+/// A scope that bounds the memory retained by the current thread: when it is dropped, every
+/// object retired by this thread (including those retired inside the scope) is submitted for
+/// reclamation, rather than waiting for the thread's batch to fill up.
+///
+/// The flush respects outstanding protection. If a [`Snapshot`] created inside the scope escapes
+/// it, the object that it protects is reclaimed when the `Snapshot` is dropped rather than when
+/// the scope ends; objects retired in the scope that are not protected are reclaimed immediately
+/// (or, if another thread is in a critical section, as soon as it leaves it).
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::Ordering::SeqCst;
+/// use aarc::smr::standard_reclaimer::ReclaimScope;
+/// use aarc::{Arc, AtomicArc};
+///
+/// let atomic = AtomicArc::new(Some(0));
+/// for i in 1..=3 {
+///     let _scope = ReclaimScope::enter();
+///     atomic.store(Some(&Arc::new(i)), SeqCst);
+/// }
+/// ```
+///
+/// [`Snapshot`]: `crate::Snapshot`
+pub struct ReclaimScope {
+    // Retirements are tracked per thread, so the scope must end on the thread that entered it.
+    phantom: PhantomData<*const ()>,
+}
+
+impl ReclaimScope {
+    /// Enters a new scope on the current thread.
+    pub fn enter() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl Drop for ReclaimScope {
+    fn drop(&mut self) {
+        StandardReclaimer::flush();
+    }
+}
 
 #[derive(Default)]
-struct Slot {
+struct Slot<const SNAPS: usize> {
     primary_list: CollectionList,
     batch: RefCell<Batch>,
-    snapshots: UnrolledLinkedList<SnapshotPtr, SNAPSHOT_PTRS_PER_NODE>,
-    // TODO: snapshots could share entries if their pointers are equal
-    // snapshots_by_addr_count: RefCell<HashMap<usize, usize>>,
+    snapshots: UnrolledLinkedList<SnapshotPtr, SNAPS>,
+    // Where the owning thread resumes its search for a free entry in `snapshots`.
+    next_snapshot_index: Cell<usize>,
+    // See `TunableReclaimer::set_snapshots_limit`.
+    snapshots_limit: Cell<Option<usize>>,
+    // Snapshots of equal pointers share an entry. This maps each pointer to the entry that was
+    // most recently claimed for it, which may since have been released.
+    snapshots_by_addr: RefCell<HashMap<*mut u8, &'static SnapshotPtr, BuildAddrHasher>>,
+    // The number of objects retired by the owning thread that have not been reclaimed yet.
+    retired_count: AtomicUsize,
     is_in_critical_section: AtomicBool,
+    // The number of nested critical sections that the owning thread is in.
+    critical_section_depth: Cell<usize>,
     is_claimed: AtomicBool,
+    // The id of the thread that claimed the slot after having released its own on exit, or 0.
+    exiting_owner: AtomicUsize,
+}
+
+impl<const SNAPS: usize> Slot<SNAPS> {
+    /// Records `batch` as a conflict of each snapshot that protects one of its pointers.
+    fn record_conflicts(&self, batch: &UnsafeArc<Batch>) {
+        for snapshot_ptr in self.snapshots.iter(SeqCst) {
+            let ptr = snapshot_ptr.ptr.load(SeqCst);
+            if !ptr.is_null() && batch.ptrs.contains(&ptr) {
+                // TODO: figure out how to do this by moving instead of cloning (RefCell?)
+                snapshot_ptr.conflicts.insert(batch.clone(), None);
+            }
+        }
+    }
 }
 
-unsafe impl Send for Slot {}
-unsafe impl Sync for Slot {}
+/// A hasher for addresses, which are already well distributed apart from their alignment bits. It
+/// is much cheaper than the default hasher, which matters because every snapshot is looked up.
+#[derive(Default)]
+struct AddrHasher(u64);
+
+impl Hasher for AddrHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 << 8) | u64::from(b);
+        }
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        // Fibonacci hashing spreads the significant bits into the high bits used by the table.
+        self.0 = (i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    }
+}
+
+type BuildAddrHasher = BuildHasherDefault<AddrHasher>;
+
+unsafe impl<const SNAPS: usize> Send for Slot<SNAPS> {}
+unsafe impl<const SNAPS: usize> Sync for Slot<SNAPS> {}
+
+/// A [`Slot`] of any size, whose conflicts must be recorded when a batch is detached from it.
+trait ConflictRecorder {
+    fn record_conflicts(&self, batch: &UnsafeArc<Batch>);
+}
+
+impl<const SNAPS: usize> ConflictRecorder for Slot<SNAPS> {
+    fn record_conflicts(&self, batch: &UnsafeArc<Batch>) {
+        Slot::record_conflicts(self, batch);
+    }
+}
 
 #[derive(Default)]
 pub struct SnapshotPtr {
     ptr: AtomicPtr<u8>,
     conflicts: CollectionList,
+    epoch: AtomicUsize,
+    // The number of snapshots sharing this entry. Only the owning thread accesses it.
+    count: AtomicUsize,
 }
 
 impl Release for SnapshotPtr {
     fn release(&self) {
-        self.ptr.store(null_mut(), SeqCst);
-        self.conflicts.detach_head();
+        if self.count.fetch_sub(1, Relaxed) == 1 {
+            self.ptr.store(null_mut(), SeqCst);
+            self.conflicts.detach_head();
+        }
+    }
+
+    fn release_many(handles: &[&Self]) {
+        // Clear every protection first so that a single fence orders all of the stores before the
+        // conflict lists are inspected. Most lists will be empty, so the swap is usually avoided.
+        for handle in handles {
+            if handle.count.fetch_sub(1, Relaxed) == 1 {
+                handle.ptr.store(null_mut(), Ordering::Release);
+            }
+        }
+        fence(SeqCst);
+        for handle in handles {
+            // Entries that are still shared by other snapshots remain protected.
+            if handle.ptr.load(Relaxed).is_null() && !handle.conflicts.head.load(SeqCst).is_null() {
+                handle.conflicts.detach_head();
+            }
+        }
     }
 }
 
@@ -153,7 +952,11 @@ struct CollectionList {
 }
 
 impl CollectionList {
-    fn insert(&self, batch: UnsafeArc<Batch>, check_on_drop: Option<&'static Slot>) {
+    fn insert(
+        &self,
+        batch: UnsafeArc<Batch>,
+        check_on_drop: Option<&'static dyn ConflictRecorder>,
+    ) {
         let mut new = UnsafeArc::new(
             CollectionNode {
                 batch,
@@ -182,46 +985,108 @@ impl CollectionList {
 struct CollectionNode {
     batch: UnsafeArc<Batch>,
     next: Option<UnsafeArc<CollectionNode>>,
-    check_on_drop: Option<&'static Slot>,
+    check_on_drop: Option<&'static dyn ConflictRecorder>,
 }
 
 impl Drop for CollectionNode {
     fn drop(&mut self) {
         if let Some(slot) = self.check_on_drop {
-            for snapshot_ptr in slot.snapshots.iter(SeqCst) {
-                let ptr = snapshot_ptr.ptr.load(SeqCst);
-                if !ptr.is_null() && self.batch.ptrs.contains(&ptr) {
-                    // TODO: figure out how to do this by moving instead of cloning (RefCell?)
-                    snapshot_ptr.conflicts.insert(self.batch.clone(), None);
-                }
-            }
+            slot.record_conflicts(&self.batch);
         }
     }
 }
 
+type Functions = Vec<Box<dyn Fn()>>;
+
 #[derive(Default)]
 struct Batch {
-    functions: Vec<Box<dyn Fn()>>,
+    functions: Functions,
     ptrs: HashSet<*mut u8>,
+    // The count to decrease when the batch is reclaimed, i.e. that of the retiring thread's slot.
+    retired_count: Option<&'static AtomicUsize>,
+}
+
+impl Batch {
+    fn run(functions: &[Box<dyn Fn()>]) {
+        for f in functions {
+            // A panicking destructor must not prevent the rest of the batch from being reclaimed.
+            // The panic hook has already reported the panic by the time it is caught here.
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| (**f)()));
+        }
+    }
 }
 
 impl Drop for Batch {
     fn drop(&mut self) {
-        for f in self.functions.iter() {
-            (**f)();
+        // Reclaiming an object may drop another batch on this thread (e.g. when chains of `Arc`s
+        // are dropped). Running its functions there would recurse once per link, so they are
+        // queued for the outermost drop to run once the current functions have finished.
+        let mut functions = mem::take(&mut self.functions);
+        if let Some(retired_count) = self.retired_count {
+            retired_count.fetch_sub(functions.len(), SeqCst);
+        }
+        let is_outermost = DEFERRED_FUNCTIONS.try_with(|deferred| {
+            let mut deferred = deferred.borrow_mut();
+            match deferred.as_mut() {
+                Some(queued) => {
+                    queued.append(&mut functions);
+                    false
+                }
+                None => {
+                    *deferred = Some(Vec::new());
+                    true
+                }
+            }
+        });
+        match is_outermost {
+            Ok(true) => {
+                while !functions.is_empty() {
+                    Self::run(&functions);
+                    functions = DEFERRED_FUNCTIONS
+                        .with(|deferred| mem::take(deferred.borrow_mut().as_mut().unwrap()));
+                }
+                DEFERRED_FUNCTIONS.with(|deferred| *deferred.borrow_mut() = None);
+            }
+            Ok(false) => {}
+            // The thread is exiting, so there is no queue to defer to.
+            Err(_) => Self::run(&functions),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::smr::drc::{Protect, ProtectPtr, Release, Retire};
-    use crate::smr::standard_reclaimer::{Batch, StandardReclaimer};
+    use crate::smr::drc::{CriticalSection, Protect, ProtectPtr, Release, Retire};
+    use crate::smr::standard_reclaimer::{
+        Batch, IsolatedReclaimer, ReclaimScope, SnapshotPtr, StandardReclaimer, TunableReclaimer,
+    };
+    use crate::utils::test_utils::DropCounter;
+    use crate::{Arc, AtomicArc, Snapshot};
     use std::alloc::{dealloc, Layout};
-    use std::cell::Cell;
+    use std::cell::{Cell, RefCell};
     use std::collections::HashSet;
     use std::ptr::null_mut;
+    use std::sync::atomic::AtomicUsize;
     use std::sync::atomic::Ordering::SeqCst;
+    use std::thread;
+
+    impl<const SLOTS: usize, const SNAPS: usize, const BATCH: usize, Domain: 'static>
+        TunableReclaimer<SLOTS, SNAPS, BATCH, Domain>
+    {
+        /// Gives the current thread an empty batch with room for `capacity` objects. The thread
+        /// may have taken over the slot of an exited thread, so the batch is flushed first.
+        fn reset_batch(capacity: usize) {
+            let slot = Self::get_or_claim_slot();
+            while !slot.batch.borrow().functions.is_empty() {
+                Self::flush();
+            }
+            slot.batch.replace(Batch {
+                functions: Vec::with_capacity(capacity),
+                ptrs: HashSet::with_capacity(capacity),
+                retired_count: None,
+            });
+        }
+    }
 
     fn with_flag<F: Fn(&'static mut Cell<bool>)>(f: F) {
         let flag: &'static mut Cell<bool> = Box::leak(Box::new(Cell::new(false)));
@@ -244,6 +1109,11 @@ mod tests {
             StandardReclaimer::retire(dummy_ptr, Box::new(|| flag.set(true)));
             assert!(!flag.get());
 
+            // Nested critical sections don't end protection.
+            let nested = CriticalSection::<StandardReclaimer>::enter_in();
+            drop(nested);
+            assert!(slot.is_in_critical_section.load(SeqCst));
+
             StandardReclaimer::end_critical_section();
             assert!(!slot.is_in_critical_section.load(SeqCst));
 
@@ -252,15 +1122,36 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_batch_size() {
+        struct Marker;
+        type Reclaimer = IsolatedReclaimer<Marker, 32, 8, 4>;
+        static RECLAIMED: AtomicUsize = AtomicUsize::new(0);
+        let targets = [0u8; 5];
+        let retire = |i: usize| {
+            let ptr = targets[i..].as_ptr() as *mut u8;
+            Reclaimer::retire(ptr, Box::new(|| _ = RECLAIMED.fetch_add(1, SeqCst)));
+        };
+
+        // The thread starts with an empty batch, which is dispatched by the first retirement.
+        retire(0);
+        assert_eq!(RECLAIMED.load(SeqCst), 1);
+        for i in 1..4 {
+            retire(i);
+        }
+        assert_eq!(RECLAIMED.load(SeqCst), 1);
+        assert_eq!(Reclaimer::pending_retired_count(), 3);
+        retire(4);
+        assert_eq!(RECLAIMED.load(SeqCst), 5);
+        assert_eq!(Reclaimer::pending_retired_count(), 0);
+    }
+
     #[test]
     fn test_protect_ptr_and_release() {
         with_flag(|flag| {
             let dummy_ptr = (flag as *const Cell<bool>) as *mut u8;
 
-            StandardReclaimer::get_or_claim_slot().batch.replace(Batch {
-                functions: Vec::with_capacity(1),
-                ptrs: HashSet::with_capacity(1),
-            });
+            StandardReclaimer::reset_batch(1);
 
             let handle = StandardReclaimer::protect_ptr(dummy_ptr);
             assert_eq!(handle.ptr.load(SeqCst), dummy_ptr);
@@ -273,4 +1164,337 @@ mod tests {
             assert!(flag.get());
         });
     }
+
+    #[test]
+    fn test_protect_ptr_and_release_many() {
+        with_flag(|flag| {
+            let dummy_ptr = (flag as *const Cell<bool>) as *mut u8;
+
+            StandardReclaimer::reset_batch(1);
+
+            let handles = [
+                StandardReclaimer::protect_ptr(dummy_ptr),
+                StandardReclaimer::protect_ptr(dummy_ptr),
+            ];
+
+            StandardReclaimer::retire(dummy_ptr, Box::new(|| flag.set(true)));
+            assert!(!flag.get());
+
+            SnapshotPtr::release_many(&handles);
+            for handle in handles {
+                assert_eq!(handle.ptr.load(SeqCst), null_mut());
+            }
+            assert!(flag.get());
+        });
+    }
+
+    #[test]
+    fn test_long_lived_snapshots_count() {
+        with_flag(|flag| {
+            let dummy_ptr = (flag as *const Cell<bool>) as *mut u8;
+
+            let handle = StandardReclaimer::protect_ptr(dummy_ptr);
+            StandardReclaimer::long_lived_snapshots_count();
+            // Other tests may hold snapshots concurrently, so only a lower bound can be checked.
+            assert!(StandardReclaimer::long_lived_snapshots_count() >= 1);
+            handle.release();
+        });
+    }
+
+    #[test]
+    fn test_reclaim_scope() {
+        with_flag(|flag| {
+            let dummy_ptr = (flag as *const Cell<bool>) as *mut u8;
+
+            StandardReclaimer::reset_batch(8);
+
+            let scope = ReclaimScope::enter();
+            StandardReclaimer::retire(dummy_ptr, Box::new(|| flag.set(true)));
+            assert!(!flag.get());
+
+            drop(scope);
+            assert!(flag.get());
+        });
+    }
+
+    #[test]
+    fn test_reclaim_scope_with_escaped_snapshot() {
+        with_flag(|flag| {
+            let dummy_ptr = (flag as *const Cell<bool>) as *mut u8;
+
+            StandardReclaimer::reset_batch(8);
+
+            let scope = ReclaimScope::enter();
+            let handle = StandardReclaimer::protect_ptr(dummy_ptr);
+            StandardReclaimer::retire(dummy_ptr, Box::new(|| flag.set(true)));
+
+            drop(scope);
+            assert!(!flag.get());
+
+            handle.release();
+            assert!(flag.get());
+        });
+    }
+
+    #[test]
+    fn test_tunable_reclaimers_are_independent() {
+        // The same parameters as `StandardReclaimer`, apart from the domain.
+        struct Marker;
+        type Separate = IsolatedReclaimer<Marker>;
+        with_flag(|flag| {
+            let dummy_ptr = (flag as *const Cell<bool>) as *mut u8;
+
+            assert!(!std::ptr::eq(
+                Separate::get_all_slots() as *const _ as *const u8,
+                StandardReclaimer::get_all_slots() as *const _ as *const u8
+            ));
+            Separate::reset_batch(1);
+
+            // A protection under the default reclaimer does not affect this one.
+            let handle = StandardReclaimer::protect_ptr(dummy_ptr);
+            Separate::retire(dummy_ptr, Box::new(|| flag.set(true)));
+            assert!(flag.get());
+            handle.release();
+        });
+    }
+
+    #[test]
+    fn test_equal_ptrs_share_snapshot_entry() {
+        with_flag(|flag| {
+            let dummy_ptr = (flag as *const Cell<bool>) as *mut u8;
+
+            StandardReclaimer::reset_batch(1);
+
+            let h1 = StandardReclaimer::protect_ptr(dummy_ptr);
+            let h2 = StandardReclaimer::protect_ptr(dummy_ptr);
+            assert!(std::ptr::eq(h1, h2));
+
+            StandardReclaimer::retire(dummy_ptr, Box::new(|| flag.set(true)));
+            h1.release();
+            assert_eq!(h2.ptr.load(SeqCst), dummy_ptr);
+            assert!(!flag.get());
+
+            h2.release();
+            assert_eq!(h2.ptr.load(SeqCst), null_mut());
+            assert!(flag.get());
+        });
+    }
+
+    #[test]
+    fn test_snapshot_clones_share_entry() {
+        struct Marker;
+        type Single = IsolatedReclaimer<Marker>;
+
+        let atomic = AtomicArc::from(&Arc::<_, Single>::new_in(53));
+        let snapshot = atomic.load::<Snapshot<_, Single>>(SeqCst).unwrap();
+        let clones: Vec<_> = (0..8).map(|_| snapshot.clone()).collect();
+        assert_eq!(Single::get_or_claim_slot().snapshots.get_nodes_count(), 1);
+
+        // The entry stays protected until the last clone is dropped.
+        drop(snapshot);
+        let entry = Single::get_or_claim_slot()
+            .snapshots
+            .iter(SeqCst)
+            .next()
+            .unwrap();
+        assert!(!entry.ptr.load(SeqCst).is_null());
+        drop(clones);
+        assert!(entry.ptr.load(SeqCst).is_null());
+    }
+
+    #[test]
+    fn test_contended_fetch_update_bounds_snapshots() {
+        struct Marker;
+        type Contended = IsolatedReclaimer<Marker>;
+        const THREADS_COUNT: usize = 4;
+        const ITERATIONS: usize = 100;
+
+        let atomic = AtomicArc::from(&Arc::<_, Contended>::new_in(0));
+        thread::scope(|s| {
+            for _ in 0..THREADS_COUNT {
+                s.spawn(|| {
+                    for _ in 0..ITERATIONS {
+                        let _ = atomic.fetch_update(SeqCst, SeqCst, |x| {
+                            Some(Some(Arc::<_, Contended>::new_in(**x? + 1)))
+                        });
+                    }
+                });
+            }
+        });
+        assert_eq!(
+            *atomic.load::<Arc<_, Contended>>(SeqCst).unwrap(),
+            THREADS_COUNT * ITERATIONS
+        );
+        // Each retry holds at most the previous and the newly observed snapshot, so no thread
+        // should ever need more than one node of entries.
+        for slot in Contended::get_all_slots().iter(SeqCst) {
+            assert_eq!(slot.snapshots.get_nodes_count(), 1);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_snapshots_warning() {
+        struct Marker;
+        // With one entry per node, the warning reports the exact number of entries.
+        type Warned = IsolatedReclaimer<Marker, 32, 1>;
+        static WARNINGS: std::sync::Mutex<Vec<usize>> = std::sync::Mutex::new(Vec::new());
+        Warned::set_snapshots_warning(2, |entries| WARNINGS.lock().unwrap().push(entries));
+
+        let atomics: Vec<_> = (0..3)
+            .map(|i| AtomicArc::<_, Warned>::from(&Arc::new_in(i)))
+            .collect();
+        let snapshots: Vec<Snapshot<_, Warned>> =
+            atomics.iter().map(|a| a.load(SeqCst).unwrap()).collect();
+        Warned::set_snapshots_warning(1024, super::warn_of_snapshots);
+        assert!(WARNINGS.lock().unwrap().contains(&3));
+        drop(snapshots);
+    }
+
+    #[test]
+    fn test_snapshots_limit() {
+        struct Marker;
+        type Limited = IsolatedReclaimer<Marker, 32, 4>;
+        Limited::set_snapshots_limit(Some(4));
+
+        let arcs: Vec<_> = (0..6).map(Arc::<_, Limited>::new_in).collect();
+        let atomics: Vec<_> = arcs.iter().map(AtomicArc::from).collect();
+        let snapshots: Vec<Snapshot<_, Limited>> =
+            atomics.iter().map(|a| a.load(SeqCst).unwrap()).collect();
+        assert_eq!(Limited::get_or_claim_slot().snapshots.get_nodes_count(), 1);
+        let strong_counts: Vec<_> = arcs.iter().map(Arc::strong_count).collect();
+        assert_eq!(strong_counts, [2, 2, 2, 2, 3, 3]);
+        // A pointer that is already protected shares its entry instead.
+        let shared = atomics[0].load::<Snapshot<_, Limited>>(SeqCst).unwrap();
+        assert_eq!(Arc::strong_count(&arcs[0]), 2);
+        assert_eq!(Limited::local_snapshots_count(), 4);
+
+        drop(shared);
+        Snapshot::release_all(snapshots);
+        assert_eq!(Limited::local_snapshots_count(), 0);
+        assert!(arcs.iter().all(|arc| Arc::strong_count(arc) == 2));
+
+        Limited::set_snapshots_limit(None);
+        let snapshots: Vec<Snapshot<_, Limited>> =
+            atomics.iter().map(|a| a.load(SeqCst).unwrap()).collect();
+        assert_eq!(Limited::get_or_claim_slot().snapshots.get_nodes_count(), 2);
+        assert!(arcs.iter().all(|arc| Arc::strong_count(arc) == 2));
+        drop(snapshots);
+    }
+
+    #[test]
+    fn test_reclaiming_arc_retires_allocation() {
+        struct Marker;
+        type Counted = IsolatedReclaimer<Marker>;
+
+        // The first batch is dispatched as soon as something is retired.
+        drop(Arc::<_, Counted>::new_in(0));
+        Counted::flush();
+        assert_eq!(Counted::pending_retired_count(), 0);
+
+        // Without weak references, the value is dropped first and then the allocation is freed,
+        // each after waiting for the protections that existed when it was retired.
+        drop(Arc::<_, Counted>::new_in(1));
+        assert_eq!(Counted::pending_retired_count(), 1);
+        Counted::flush();
+        assert_eq!(Counted::pending_retired_count(), 1);
+        Counted::flush();
+        assert_eq!(Counted::pending_retired_count(), 0);
+
+        // The last weak reference retires the allocation instead.
+        let x = Arc::<_, Counted>::new_in(2);
+        let w = Arc::downgrade(&x);
+        drop(x);
+        Counted::flush();
+        assert_eq!(Counted::pending_retired_count(), 0);
+        drop(w);
+        assert_eq!(Counted::pending_retired_count(), 1);
+        Counted::flush();
+        assert_eq!(Counted::pending_retired_count(), 0);
+    }
+
+    #[test]
+    fn test_prewarm() {
+        struct Marker;
+        type Prewarmed = IsolatedReclaimer<Marker>;
+
+        thread::spawn(|| {
+            Prewarmed::prewarm();
+            assert_eq!(Prewarmed::slots_in_use(), 1);
+            Prewarmed::prewarm();
+            assert_eq!(Prewarmed::slots_in_use(), 1);
+        })
+        .join()
+        .unwrap();
+        assert_eq!(Prewarmed::slots_in_use(), 0);
+    }
+
+    #[test]
+    fn test_memory_introspection() {
+        struct Marker;
+        type Introspected = IsolatedReclaimer<Marker>;
+
+        thread::spawn(|| drop(Arc::<_, Introspected>::new_in(0)))
+            .join()
+            .unwrap();
+        // The thread released its slot when it exited, but its batch still holds the allocation
+        // that was retired when the value was reclaimed.
+        assert_eq!(Introspected::slots_in_use(), 0);
+        assert_eq!(Introspected::pending_retired_count(), 1);
+
+        Introspected::flush();
+        assert_eq!(Introspected::slots_in_use(), 1);
+        assert_eq!(Introspected::pending_retired_count(), 0);
+    }
+
+    #[test]
+    fn test_exited_threads_batches_are_reclaimed() {
+        struct Marker;
+        type Exiting = IsolatedReclaimer<Marker>;
+
+        thread::spawn(|| {
+            // A thread's first batch is dispatched immediately, and the next holds 32 objects.
+            drop(Arc::<_, Exiting>::new_in(0));
+            for _ in 0..5 {
+                drop(Arc::<_, Exiting>::new_in(DropCounter::<Marker>::new()));
+            }
+        })
+        .join()
+        .unwrap();
+        assert_eq!(DropCounter::<Marker>::drops(), 0);
+
+        Exiting::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 5);
+    }
+
+    #[test]
+    fn test_retire_from_thread_local_destructor() {
+        struct Marker;
+        type Exiting = IsolatedReclaimer<Marker>;
+        struct Held(AtomicArc<DropCounter<Marker>, Exiting>);
+        impl Drop for Held {
+            fn drop(&mut self) {
+                let snapshot = self.0.load::<Snapshot<_, Exiting>>(SeqCst);
+                assert!(snapshot.is_some());
+            }
+        }
+        thread_local! {
+            static HELD: RefCell<Option<Held>> = const { RefCell::new(None) };
+        }
+
+        thread::spawn(|| {
+            // Setting this first means that it is destroyed after the reclaimer's thread-locals.
+            HELD.set(Some(Held(AtomicArc::from(&Arc::new_in(DropCounter::<
+                Marker,
+            >::new(
+            ))))));
+            let atomic = AtomicArc::from(&Arc::<_, Exiting>::new_in(0));
+            atomic.store::<Arc<_, Exiting>>(None, SeqCst);
+        })
+        .join()
+        .unwrap();
+
+        Exiting::flush();
+        assert_eq!(DropCounter::<Marker>::drops(), 1);
+    }
 }
@@ -0,0 +1,99 @@
+use crate::smr::drc::{Protect, ProtectPtr, Retire};
+use crate::{AtomicArc, Snapshot};
+use core::sync::atomic::Ordering::SeqCst;
+
+default_reclaimer! {
+    /// An iterator that follows a chain of nodes linked by [`AtomicArc`]s, yielding a [`Snapshot`]
+    /// of each node until it reaches a null link.
+    ///
+    /// `link` projects a node to the `AtomicArc` that points to its successor. The iterator holds
+    /// a single `Snapshot` of its own (the next node to yield), so traversing a long chain does
+    /// not exhaust the reclaimer's protections, as long as the caller drops the yielded
+    /// `Snapshot`s as it goes.
+    ///
+    /// A node's link is loaded as soon as the node is yielded. Nodes that are inserted or removed
+    /// further along the chain during traversal may or may not be observed.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::Ordering::SeqCst;
+    /// use aarc::{Arc, AtomicArc, SnapshotIter};
+    ///
+    /// #[derive(Default)]
+    /// struct Node {
+    ///     val: usize,
+    ///     next: AtomicArc<Self>,
+    /// }
+    ///
+    /// let head = AtomicArc::default();
+    /// for val in 0..3 {
+    ///     let next = head.take(SeqCst);
+    ///     let next = next.as_ref().map_or(AtomicArc::default(), AtomicArc::from);
+    ///     head.store(Some(&Arc::new(Node { val, next })), SeqCst);
+    /// }
+    ///
+    /// let iter = SnapshotIter::from_atomic(&head, |node| &node.next);
+    /// let vals: Vec<_> = iter.map(|node| node.val).collect();
+    /// assert_eq!(vals, [2, 1, 0]);
+    /// ```
+    pub struct SnapshotIter<T: 'static, R: Protect + ProtectPtr + Retire> {
+        next: Option<Snapshot<T, R>>,
+        link: fn(&T) -> &AtomicArc<T, R>,
+    }
+}
+
+impl<T: 'static, R: Protect + ProtectPtr + Retire> SnapshotIter<T, R> {
+    /// Creates an iterator that begins at `start` (inclusive).
+    pub fn new(start: Option<Snapshot<T, R>>, link: fn(&T) -> &AtomicArc<T, R>) -> Self {
+        Self { next: start, link }
+    }
+
+    /// Creates an iterator that begins at the node that `atomic` points to.
+    pub fn from_atomic(atomic: &AtomicArc<T, R>, link: fn(&T) -> &AtomicArc<T, R>) -> Self {
+        Self::new(atomic.load(SeqCst), link)
+    }
+}
+
+impl<T: 'static, R: Protect + ProtectPtr + Retire> Iterator for SnapshotIter<T, R> {
+    type Item = Snapshot<T, R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr = self.next.take()?;
+        self.next = (self.link)(&curr).load(SeqCst);
+        Some(curr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, AtomicArc, Snapshot, SnapshotIter};
+    use std::sync::atomic::Ordering::SeqCst;
+
+    #[derive(Default)]
+    struct Node {
+        val: usize,
+        next: AtomicArc<Self>,
+    }
+
+    #[test]
+    fn test_snapshot_iter_loads_links_eagerly() {
+        let first = Arc::new(Node::default());
+        let mut iter = SnapshotIter::new(Some(Snapshot::from(&first)), |node| &node.next);
+        assert_eq!(iter.next().map(|node| node.val), Some(0));
+
+        // The first node's link was loaded when it was yielded, so the append isn't observed.
+        first.next.store(
+            Some(&Arc::new(Node {
+                val: 1,
+                next: AtomicArc::default(),
+            })),
+            SeqCst,
+        );
+        assert!(iter.next().is_none());
+
+        let vals: Vec<_> = SnapshotIter::new(Some(Snapshot::from(&first)), |node| &node.next)
+            .map(|node| node.val)
+            .collect();
+        assert_eq!(vals, [0, 1]);
+    }
+}
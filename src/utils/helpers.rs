@@ -1,3 +1,5 @@
+use alloc::boxed::Box;
+
 pub(crate) fn alloc_box_ptr<T>(item: T) -> *mut T {
     Box::into_raw(Box::new(item))
 }
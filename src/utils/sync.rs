@@ -0,0 +1,8 @@
+//! Atomic types that are swapped out for their `loom` counterparts under `--cfg loom`.
+
+#[cfg(loom)]
+pub(crate) use loom::sync::atomic::{fence, AtomicBool, AtomicIsize, AtomicPtr, AtomicUsize};
+
+#[cfg(not(loom))]
+#[cfg_attr(not(feature = "std"), allow(unused_imports))]
+pub(crate) use core::sync::atomic::{fence, AtomicBool, AtomicIsize, AtomicPtr, AtomicUsize};
@@ -0,0 +1,29 @@
+use std::any::TypeId;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+static DROPS: Mutex<BTreeMap<TypeId, usize>> = Mutex::new(BTreeMap::new());
+
+/// A zero-sized value that records its drop, so that tests can check when (and how often) values
+/// are reclaimed. Drops are counted per marker type `M`, so each test should use its own marker.
+pub(crate) struct DropCounter<M: 'static>(PhantomData<fn() -> M>);
+
+impl<M: 'static> DropCounter<M> {
+    pub(crate) fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    /// Returns the number of values with the marker `M` that have been dropped.
+    pub(crate) fn drops() -> usize {
+        let drops = DROPS.lock().unwrap();
+        drops.get(&TypeId::of::<M>()).copied().unwrap_or(0)
+    }
+}
+
+impl<M: 'static> Drop for DropCounter<M> {
+    fn drop(&mut self) {
+        let mut drops = DROPS.lock().unwrap();
+        *drops.entry(TypeId::of::<M>()).or_default() += 1;
+    }
+}
@@ -1,8 +1,9 @@
 use crate::utils::helpers::{alloc_box_ptr, dealloc_box_ptr};
-use std::array;
-use std::ptr::null_mut;
-use std::sync::atomic::Ordering::SeqCst;
-use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use crate::utils::sync::{AtomicPtr, AtomicUsize};
+use core::ptr::{null, null_mut};
+use core::sync::atomic::Ordering::SeqCst;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::{array, slice};
 
 /// A specialized linked list; each node contains an array of N items.
 pub(crate) struct UnrolledLinkedList<T: Default, const N: usize> {
@@ -11,7 +12,7 @@ pub(crate) struct UnrolledLinkedList<T: Default, const N: usize> {
 }
 
 impl<T: Default, const N: usize> UnrolledLinkedList<T, N> {
-    pub(crate) fn iter(&self, order: Ordering) -> impl Iterator<Item = &'_ T> {
+    pub(crate) fn iter(&self, order: Ordering) -> Iter<'_, T, N> {
         self.head.iter(order)
     }
 
@@ -32,12 +33,63 @@ impl<T: Default, const N: usize> UnrolledLinkedList<T, N> {
         }
     }
 
+    /// Resets every item for which `f` returns `true` to its default (unclaimed) state and returns
+    /// the number of items that were reset.
+    ///
+    /// Nodes are never freed, so items are removed logically, and their positions can then be
+    /// reclaimed by a later call to [`try_for_each_with_append`][`Self::try_for_each_with_append`].
+    /// This is safe to call concurrently with iteration and appends. An item that is removed and
+    /// claimed again by another thread between the call to `f` and its reset is removed too.
+    #[allow(dead_code)]
+    pub(crate) fn remove_if<F: Fn(&T) -> bool>(&self, f: F) -> usize
+    where
+        T: Tombstone,
+    {
+        self.iter(SeqCst)
+            .filter(|item| f(item) && item.try_reset())
+            .count()
+    }
+
     pub(crate) fn try_for_each_with_append<F: Fn(&T) -> bool>(&self, f: F) -> &T {
+        self.try_for_each_with_append_from(0, f).1
+    }
+
+    /// Like [`try_for_each_with_append`][`Self::try_for_each_with_append`], but the search begins
+    /// at `start`. If no item from `start` onwards is accepted, the list is searched again from
+    /// the beginning before a node is appended. Returns the accepted item and its index.
+    pub(crate) fn try_for_each_with_append_from<F: Fn(&T) -> bool>(
+        &self,
+        start: usize,
+        f: F,
+    ) -> (usize, &T) {
+        let mut curr: *const ULLNode<T, N> = if start == 0 { null() } else { &self.head };
+        let mut base = 0;
+        while !curr.is_null() && base + N <= start {
+            curr = unsafe { (*curr).next.load(SeqCst) };
+            base += N;
+        }
+        while !curr.is_null() {
+            let node = unsafe { &*curr };
+            for (i, item) in node
+                .items
+                .iter()
+                .enumerate()
+                .skip(start.saturating_sub(base))
+            {
+                if f(item) {
+                    return (base + i, item);
+                }
+            }
+            curr = node.next.load(SeqCst);
+            base += N;
+        }
+
         let mut curr = &self.head;
+        let mut base = 0;
         loop {
-            for item in curr.items.iter() {
+            for (i, item) in curr.items.iter().enumerate() {
                 if f(item) {
-                    return item;
+                    return (base + i, item);
                 }
             }
             let mut next = curr.next.load(SeqCst);
@@ -60,6 +112,7 @@ impl<T: Default, const N: usize> UnrolledLinkedList<T, N> {
             unsafe {
                 curr = &*next;
             }
+            base += N;
         }
     }
 }
@@ -72,6 +125,19 @@ impl<T: Default, const N: usize> Default for UnrolledLinkedList<T, N> {
         }
     }
 }
+/// An item of an [`UnrolledLinkedList`] that can be removed with
+/// [`remove_if`][`UnrolledLinkedList::remove_if`].
+pub(crate) trait Tombstone {
+    /// Atomically resets `self` to its default state, returning `false` if it was already in it.
+    fn try_reset(&self) -> bool;
+}
+
+impl Tombstone for AtomicBool {
+    fn try_reset(&self) -> bool {
+        self.compare_exchange(true, false, SeqCst, SeqCst).is_ok()
+    }
+}
+
 unsafe impl<T: Default + Send + Sync, const N: usize> Send for UnrolledLinkedList<T, N> {}
 
 unsafe impl<T: Default + Send + Sync, const N: usize> Sync for UnrolledLinkedList<T, N> {}
@@ -82,16 +148,39 @@ struct ULLNode<T, const N: usize> {
 }
 
 impl<T, const N: usize> ULLNode<T, N> {
-    fn iter(&self, order: Ordering) -> impl Iterator<Item = &'_ T> {
-        let mut iters = vec![self.items.iter()];
-        let mut curr = self.next.load(order);
-        while !curr.is_null() {
-            unsafe {
-                iters.push((*curr).items.iter());
-                curr = (*curr).next.load(order);
+    fn iter(&self, order: Ordering) -> Iter<'_, T, N> {
+        Iter {
+            items: self.items.iter(),
+            node: self,
+            order,
+        }
+    }
+}
+
+/// An iterator over the items of an [`UnrolledLinkedList`]. Each node's `next` pointer is loaded
+/// once its items are exhausted, so nodes that are appended during iteration are also visited.
+pub(crate) struct Iter<'a, T, const N: usize> {
+    items: slice::Iter<'a, T>,
+    node: &'a ULLNode<T, N>,
+    order: Ordering,
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(item);
             }
+            let next = self.node.next.load(self.order);
+            if next.is_null() {
+                return None;
+            }
+            // Nodes are only freed with the list, which outlives the iterator.
+            self.node = unsafe { &*next };
+            self.items = self.node.items.iter();
         }
-        iters.into_iter().flatten()
     }
 }
 
@@ -118,8 +207,8 @@ impl<T, const N: usize> Drop for ULLNode<T, N> {
 #[cfg(test)]
 mod tests {
     use crate::utils::unrolled_linked_list::UnrolledLinkedList;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::Ordering::SeqCst;
+    use std::sync::atomic::{AtomicBool, AtomicUsize};
     use std::thread;
 
     #[test]
@@ -142,4 +231,69 @@ mod tests {
             assert_eq!(ull.get_at_index(i).load(SeqCst), i < THREADS_COUNT);
         }
     }
+
+    #[test]
+    fn test_concurrent_remove_if_and_append() {
+        const ITEMS_PER_NODE: usize = 2;
+        const THREADS_COUNT: usize = ITEMS_PER_NODE * 2 + 1;
+
+        let ull: UnrolledLinkedList<AtomicBool, ITEMS_PER_NODE> = UnrolledLinkedList::default();
+        let claim = |b: &AtomicBool| b.compare_exchange(false, true, SeqCst, SeqCst).is_ok();
+        let claimed = |b: &AtomicBool| b.load(SeqCst);
+        let removed = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for _ in 0..THREADS_COUNT {
+                s.spawn(|| {
+                    ull.try_for_each_with_append(claim);
+                    removed.fetch_add(ull.remove_if(claimed), SeqCst);
+                    ull.iter(SeqCst).for_each(|b| _ = b.load(SeqCst));
+                });
+            }
+        });
+        // Every claimed item was removed exactly once, so nothing remains.
+        assert_eq!(removed.load(SeqCst), THREADS_COUNT);
+        assert!(ull.iter(SeqCst).all(|b| !b.load(SeqCst)));
+
+        let nodes_count = ull.get_nodes_count();
+        for _ in 0..ITEMS_PER_NODE * nodes_count {
+            ull.try_for_each_with_append(claim);
+        }
+        // The removed positions were reused rather than appended.
+        assert_eq!(ull.get_nodes_count(), nodes_count);
+        assert_eq!(ull.remove_if(|_| true), ITEMS_PER_NODE * nodes_count);
+        assert_eq!(ull.remove_if(|_| true), 0);
+    }
+
+    #[test]
+    fn test_iter_visits_appended_nodes() {
+        let ull: UnrolledLinkedList<AtomicBool, 1> = UnrolledLinkedList::default();
+        let claim = |b: &AtomicBool| b.compare_exchange(false, true, SeqCst, SeqCst).is_ok();
+
+        let mut iter = ull.iter(SeqCst);
+        assert!(!iter.next().unwrap().load(SeqCst));
+        ull.try_for_each_with_append(claim);
+        ull.try_for_each_with_append(claim);
+        assert!(iter.next().unwrap().load(SeqCst));
+        assert!(iter.next().is_none());
+        assert_eq!(ull.iter(SeqCst).count(), 2);
+    }
+
+    #[test]
+    fn test_append_from() {
+        let ull: UnrolledLinkedList<AtomicBool, 2> = UnrolledLinkedList::default();
+        let claim = |b: &AtomicBool| b.compare_exchange(false, true, SeqCst, SeqCst).is_ok();
+
+        for i in 0..3 {
+            assert_eq!(ull.try_for_each_with_append_from(i, claim).0, i);
+        }
+        assert_eq!(ull.get_nodes_count(), 2);
+
+        ull.get_at_index(0).store(false, SeqCst);
+        assert_eq!(ull.try_for_each_with_append_from(3, claim).0, 3);
+        // Free items before `start` are reused before any node is appended.
+        assert_eq!(ull.try_for_each_with_append_from(4, claim).0, 0);
+        assert_eq!(ull.get_nodes_count(), 2);
+        assert_eq!(ull.try_for_each_with_append_from(1, claim).0, 4);
+        assert_eq!(ull.get_nodes_count(), 3);
+    }
 }
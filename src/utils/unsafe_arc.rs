@@ -1,9 +1,9 @@
 use crate::utils::helpers::{alloc_box_ptr, dealloc_box_ptr};
-use std::marker::PhantomData;
-use std::ops::{Deref, DerefMut};
-use std::ptr::NonNull;
-use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
-use std::sync::atomic::{fence, AtomicUsize};
+use crate::utils::sync::{fence, AtomicUsize};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
 
 /// A slightly more efficient and convenient Arc for internal use only.
 /// It has no weak count, implements DerefMut, and can be initialized with an arbitrary ref count.
@@ -1,3 +1,4 @@
+use aarc::collections::Stack;
 use aarc::{Arc, AtomicArc, AtomicWeak, Snapshot};
 use rand::random;
 use std::sync::atomic::AtomicUsize;
@@ -5,50 +6,6 @@ use std::sync::atomic::Ordering::SeqCst;
 use std::thread;
 
 fn test_stack(threads_count: usize, iters_per_thread: usize) {
-    struct StackNode {
-        val: usize,
-        next: Option<Arc<Self>>,
-    }
-
-    #[derive(Default)]
-    struct Stack {
-        top: AtomicArc<StackNode>,
-    }
-
-    impl Stack {
-        fn push(&self, val: usize) {
-            let mut top = self.top.load::<Snapshot<_>>(SeqCst);
-            loop {
-                let new_node = Arc::new(StackNode {
-                    val,
-                    next: top.as_ref().map(Arc::from),
-                });
-                match self
-                    .top
-                    .compare_exchange(top.as_ref(), Some(&new_node), SeqCst, SeqCst)
-                {
-                    Ok(_) => break,
-                    Err(before) => top = before,
-                }
-            }
-        }
-        fn pop(&self) -> Option<Arc<StackNode>> {
-            let mut top = self.top.load::<Arc<_>>(SeqCst);
-            while let Some(top_node) = top.as_ref() {
-                match self.top.compare_exchange(
-                    top.as_ref(),
-                    top_node.next.as_ref(),
-                    SeqCst,
-                    SeqCst,
-                ) {
-                    Ok(_) => return top,
-                    Err(actual_top) => top = actual_top,
-                }
-            }
-            None
-        }
-    }
-
     let stack = Stack::default();
 
     thread::scope(|s| {
@@ -69,7 +26,7 @@ fn test_stack(threads_count: usize, iters_per_thread: usize) {
             s.spawn(|| {
                 for _ in 0..iters_per_thread {
                     let node = stack.pop().unwrap();
-                    val_counts[node.val].fetch_add(1, SeqCst);
+                    val_counts[**node].fetch_add(1, SeqCst);
                 }
             });
         }
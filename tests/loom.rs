@@ -0,0 +1,55 @@
+#![cfg(loom)]
+
+use aarc::{Arc, AtomicArc, Snapshot};
+use loom::model::Builder;
+use loom::thread;
+use std::sync::atomic::Ordering::SeqCst;
+
+fn model<F: Fn() + Sync + Send + 'static>(f: F) {
+    let mut builder = Builder::new();
+    builder.preemption_bound = Some(3);
+    // Dispatching a batch scans every slot, which takes more branches than the default allows.
+    builder.max_branches = 10_000;
+    builder.check(f);
+}
+
+fn spawn<F: FnOnce() + Send + 'static>(f: F) -> thread::JoinHandle<()> {
+    // The reclaimer's slots are allocated in large nodes, which overflow loom's default stack.
+    thread::Builder::new().stack_size(8 << 20).spawn(f).unwrap()
+}
+
+#[test]
+fn test_concurrent_store_load_compare_exchange() {
+    model(|| {
+        let atomic = loom::sync::Arc::new(AtomicArc::new(Some(0)));
+
+        let storer = {
+            let atomic = atomic.clone();
+            spawn(move || atomic.store(Some(&Arc::new(1)), SeqCst))
+        };
+        let exchanger = {
+            let atomic = atomic.clone();
+            spawn(move || {
+                let current = atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+                assert!(*current == 0 || *current == 1);
+                let _ = atomic.compare_exchange::<_, _, Snapshot<_>>(
+                    Some(&current),
+                    Some(&Arc::new(2)),
+                    SeqCst,
+                    SeqCst,
+                );
+                // The snapshot must remain readable even if its value was replaced concurrently.
+                assert!(*current == 0 || *current == 1);
+            })
+        };
+
+        storer.join().unwrap();
+        exchanger.join().unwrap();
+        spawn(move || {
+            let last = *atomic.load::<Snapshot<_>>(SeqCst).unwrap();
+            assert!(last == 1 || last == 2);
+        })
+        .join()
+        .unwrap();
+    });
+}